@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE};
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::error::{DockerError, DockerResult};
+
+#[derive(Clone)]
+pub enum RegistryAuth {
+    Credentials(String, String),
+    IdentityToken(String),
+}
+
+// secrets never leave through Debug, so specs holding credentials
+// like ImageBuildSpec can be logged without leaking them
+impl fmt::Debug for RegistryAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegistryAuth::Credentials(username, _) => f
+                .debug_tuple("Credentials")
+                .field(username)
+                .field(&"<redacted>")
+                .finish(),
+            RegistryAuth::IdentityToken(_) => f.debug_tuple("IdentityToken").field(&"<redacted>").finish(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct DockerConfig {
+    auths: Option<HashMap<String, DockerConfigAuth>>,
+}
+
+#[derive(Deserialize)]
+struct DockerConfigAuth {
+    auth: Option<String>,
+    identitytoken: Option<String>,
+}
+
+impl fmt::Debug for DockerConfigAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DockerConfigAuth")
+            .field("auth", &self.auth.as_ref().map(|_| "<redacted>"))
+            .field("identitytoken", &self.identitytoken.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl RegistryAuth {
+    pub fn credentials(username: &str, password: &str) -> Self {
+        RegistryAuth::Credentials(username.to_owned(), password.to_owned())
+    }
+
+    pub fn identity_token(token: &str) -> Self {
+        RegistryAuth::IdentityToken(token.to_owned())
+    }
+
+    // looks up credentials stored by 'docker login' in config.json,
+    // credential helpers are not supported and resolve to nothing
+    pub async fn from_docker_config(registry: &str) -> DockerResult<Option<Self>> {
+        let path: PathBuf = match std::env::var_os("DOCKER_CONFIG") {
+            Some(value) => PathBuf::from(value).join("config.json"),
+            None => match std::env::var_os("HOME") {
+                Some(value) => PathBuf::from(value).join(".docker").join("config.json"),
+                None => return Ok(None),
+            },
+        };
+
+        let location: String = path.to_string_lossy().to_string();
+        let data: Vec<u8> = match tokio::fs::read(&path).await {
+            Ok(value) => value,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return DockerError::raise_registry_config_failed(&location, error),
+        };
+
+        let config: DockerConfig = match serde_json::from_slice(&data) {
+            Ok(value) => value,
+            Err(error) => return DockerError::raise_registry_config_invalid(&location, error.to_string()),
+        };
+
+        let entry: &DockerConfigAuth = match config.auths.as_ref().and_then(|auths| auths.get(registry)) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        if let Some(token) = &entry.identitytoken {
+            return Ok(Some(RegistryAuth::IdentityToken(token.clone())));
+        }
+
+        let auth: &str = match &entry.auth {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let decoded: Vec<u8> = match STANDARD.decode(auth) {
+            Ok(value) => value,
+            Err(error) => return DockerError::raise_registry_config_invalid(&location, error.to_string()),
+        };
+
+        match String::from_utf8_lossy(&decoded).split_once(':') {
+            Some((username, password)) => Ok(Some(RegistryAuth::credentials(username, password))),
+            None => {
+                DockerError::raise_registry_config_invalid(&location, format!("auth for '{registry}' is malformed"))
+            }
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        match self {
+            RegistryAuth::Credentials(username, password) => json!({"username": username, "password": password}),
+            RegistryAuth::IdentityToken(token) => json!({"identitytoken": token}),
+        }
+    }
+
+    pub(crate) fn encode(auth: Option<&RegistryAuth>) -> String {
+        let payload: Value = match auth {
+            Some(auth) => auth.to_json(),
+            None => json!({}),
+        };
+
+        URL_SAFE.encode(payload.to_string())
+    }
+
+    pub(crate) fn encode_config(auths: &HashMap<&str, RegistryAuth>) -> String {
+        let mut payload: Value = json!({});
+
+        for (registry, auth) in auths.iter() {
+            payload[*registry] = auth.to_json();
+        }
+
+        URL_SAFE.encode(payload.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::types::ImageBuildSpec;
+
+    #[test]
+    fn redacts_secrets_in_debug() {
+        let credentials: RegistryAuth = RegistryAuth::credentials("admin", "s3cret");
+        let token: RegistryAuth = RegistryAuth::identity_token("t0ken");
+
+        assert_eq!(format!("{credentials:?}"), r#"Credentials("admin", "<redacted>")"#);
+        assert_eq!(format!("{token:?}"), r#"IdentityToken("<redacted>")"#);
+    }
+
+    #[test]
+    fn redacts_secrets_in_build_spec_debug() {
+        let spec: ImageBuildSpec =
+            ImageBuildSpec::new().registry_auth("ghcr.io", RegistryAuth::credentials("admin", "s3cret"));
+        let debug: String = format!("{spec:?}");
+
+        assert!(debug.contains("admin"));
+        assert!(!debug.contains("s3cret"));
+    }
+}
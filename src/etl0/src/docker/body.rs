@@ -0,0 +1,38 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::Stream;
+use hyper::body::{Body, Bytes, Frame};
+
+use super::error::{DockerError, DockerResult};
+
+pub struct StreamBody {
+    inner: Pin<Box<dyn Stream<Item = DockerResult<Bytes>> + Send>>,
+}
+
+impl StreamBody {
+    pub fn from<S>(stream: S) -> Self
+    where
+        S: Stream<Item = DockerResult<Bytes>> + Send + 'static,
+    {
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+}
+
+impl Body for StreamBody {
+    type Data = Bytes;
+    type Error = DockerError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let self_mut: &mut StreamBody = self.get_mut();
+
+        match self_mut.inner.as_mut().poll_next(cx) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Ready(Some(Err(error))) => Poll::Ready(Some(Err(error))),
+            Poll::Ready(Some(Ok(data))) => Poll::Ready(Some(Ok(Frame::data(data)))),
+        }
+    }
+}
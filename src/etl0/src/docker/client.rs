@@ -1,30 +1,206 @@
 use http_body_util::Full;
-use hyper::body::Bytes;
+use hyper::body::{Body, Bytes};
 use serde_json::{json, Value};
+use tokio::io::split;
+use tokio::time::sleep;
+use tokio_stream::{Stream, StreamExt};
 
+use super::auth::RegistryAuth;
+use super::body::StreamBody;
+use super::endpoint::DockerEndpoint;
 use super::error::{DockerError, DockerResult};
-use super::http::DockerConnection;
-use super::stream::{ContainerLogsStream, ImageCreateStream};
+use super::http::{DockerConnection, DockerPool};
+use super::mirror::RegistryMirror;
+use super::owner::DockerOwner;
+use super::query::{encode, encode_json};
+use super::stdin::ContainerStdin;
+use super::stream::{ContainerArchiveStream, ContainerAttachStream, ContainerDownloadStream, ContainerLogsStream};
+use super::stream::{ImageBuildStream, ImageCreateStream};
+use super::system::{parse_api_version, SystemVersionResponse};
 use super::tar::TarBody;
 use super::types::*;
 use crate::tar::{TarArchive, TarStream};
 
-#[derive(Debug)]
+// the newest API version whose endpoints the client was written against
+const DOCKER_API_VERSION: (u32, u32) = (1, 42);
+
+// how many idle connections a client keeps open for later requests
+const DOCKER_POOL_SIZE: usize = 4;
+
+#[derive(Debug, Clone)]
 pub struct DockerClient {
-    socket: String,
+    pub(super) endpoint: DockerEndpoint,
+    limits: DockerResponseLimits,
+    pub(super) prefix: String,
+    pub(super) owner: Option<DockerOwner>,
+    labels: Vec<(String, String)>,
+    mirror: Option<RegistryMirror>,
+    pool: DockerPool,
 }
 
 impl DockerClient {
     pub fn open(socket: &str) -> Self {
+        Self::from_endpoint(DockerEndpoint::unix(socket))
+    }
+
+    pub fn from_env() -> DockerResult<Self> {
+        Ok(Self::from_endpoint(DockerEndpoint::from_env()?))
+    }
+
+    pub fn from_endpoint(endpoint: DockerEndpoint) -> Self {
+        Self {
+            endpoint,
+            limits: DockerResponseLimits::default(),
+            prefix: format!("/v{}.{}", DOCKER_API_VERSION.0, DOCKER_API_VERSION.1),
+            owner: None,
+            labels: Vec::new(),
+            mirror: None,
+            pool: DockerPool::new(DOCKER_POOL_SIZE),
+        }
+    }
+
+    pub fn with_limits(self, limits: DockerResponseLimits) -> Self {
+        Self { limits: limits, ..self }
+    }
+
+    // zero disables reuse and every request opens its own connection
+    pub fn with_pool_size(self, size: usize) -> Self {
+        Self {
+            pool: DockerPool::new(size),
+            ..self
+        }
+    }
+
+    pub fn with_version(self, version: &str) -> Self {
+        Self {
+            prefix: format!("/v{}", version.trim_start_matches('v')),
+            ..self
+        }
+    }
+
+    // every container, volume and network created through this client
+    // is labelled with the owner, so leftovers can be found later
+    pub fn with_owner(self, owner: DockerOwner) -> Self {
+        Self {
+            owner: Some(owner),
+            ..self
+        }
+    }
+
+    pub fn owner(&self) -> Option<&DockerOwner> {
+        self.owner.as_ref()
+    }
+
+    // default labels, such as team or cost center, are attached to every
+    // container, volume and network created through this client
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.labels.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // images are pulled from the mirror and containers are created
+    // from the mirrored name, so callers keep using the original one
+    pub fn with_mirror(self, mirror: RegistryMirror) -> Self {
         Self {
-            socket: socket.to_owned(),
+            mirror: Some(mirror),
+            ..self
         }
     }
 
-    pub async fn containers_list(&self) -> DockerResult<ContainerList> {
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+    fn redirect(&self, mut payload: Value) -> Value {
+        let (mirror, image): (&RegistryMirror, String) = match (&self.mirror, payload["Image"].as_str()) {
+            (Some(mirror), Some(image)) => (mirror, image.to_owned()),
+            _ => return payload,
+        };
+
+        let reference: ImageReference = ImageReference::parse(&image);
+
+        if let Some(name) = reference.mirror(mirror) {
+            payload["Image"] = json!(format!("{name}{}", &image[reference.name.len()..]));
+        }
+
+        payload
+    }
+
+    // labels given by the spec win over the defaults, while the owner
+    // labels always win, because cleanup relies on them
+    pub(super) fn stamp(&self, mut payload: Value) -> Value {
+        for (key, value) in self.labels.iter() {
+            if payload["Labels"][key].is_null() {
+                payload["Labels"][key] = json!(value);
+            }
+        }
+
+        if let Some(owner) = &self.owner {
+            for (key, value) in owner.labels() {
+                payload["Labels"][key] = json!(value);
+            }
+        }
+
+        payload
+    }
+
+    pub fn version(&self) -> &str {
+        self.prefix.trim_start_matches("/v")
+    }
+
+    // asks the daemon for its supported range using the unversioned
+    // endpoint and settles on the highest version both sides speak
+    pub async fn negotiate(self) -> DockerResult<Self> {
+        let url: String = String::from("/version");
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        let response: SystemVersionResponse = match connection.get(&url).await {
+            Ok(response) => response.into_json().await?,
+            Err(error) => return Err(error),
+        };
+
+        let maximum: Option<(u32, u32)> = parse_api_version(&response.api_version);
+        let minimum: Option<(u32, u32)> = match &response.min_api_version {
+            Some(value) => parse_api_version(value),
+            None => Some((0, 0)),
+        };
+
+        match (minimum, maximum) {
+            (Some(minimum), Some(maximum)) if minimum <= DOCKER_API_VERSION => {
+                let (major, minor) = std::cmp::min(maximum, DOCKER_API_VERSION);
+                Ok(self.with_version(&format!("{major}.{minor}")))
+            }
+            _ => DockerError::raise_unsupported_api_version(&response.api_version, response.min_api_version.as_deref()),
+        }
+    }
+
+    pub(super) async fn connect(&self) -> DockerResult<DockerConnection<Full<Bytes>>> {
+        DockerConnection::reuse(&self.pool, &self.endpoint, self.limits).await
+    }
+
+    // endpoints answering only once the container changes its state
+    // may legitimately take longer than any configured deadline
+    pub(super) async fn connect_long_poll(&self) -> DockerResult<DockerConnection<Full<Bytes>>> {
+        let limits: DockerResponseLimits = DockerResponseLimits {
+            timeout: None,
+            ..self.limits
+        };
+
+        DockerConnection::reuse(&self.pool, &self.endpoint, limits).await
+    }
+
+    // request bodies streamed from disk or other sources are sent
+    // over a dedicated connection, which is closed right after
+    pub(super) async fn connect_streaming<T>(&self) -> DockerResult<DockerConnection<T>>
+    where
+        T: Body + Send + 'static,
+        T::Data: Send,
+        T::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        DockerConnection::open(&self.endpoint, self.limits).await
+    }
+
+    pub async fn containers_list(&self, options: &ContainerListOptions) -> DockerResult<ContainerList> {
+        let url: String = format!("{}/containers/json?{}", self.prefix, options.to_query());
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
-        match connection.get("/v1.42/containers/json?all=true").await {
+        match connection.get(&url).await {
             Ok(response) => match response.into_json().await {
                 Ok(value) => Ok(ContainerList::Succeeded(value)),
                 Err(error) => Err(error),
@@ -41,11 +217,13 @@ impl DockerClient {
     }
 
     pub async fn containers_create(&self, spec: &ContainerCreateSpec<'_>) -> DockerResult<ContainerCreate> {
-        let url: String = format!("/v1.42/containers/create");
-        let payload: Value = json!({"Image": spec.image, "Cmd": spec.command});
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+        let url: String = format!("{}/containers/create?{}", self.prefix, spec.to_query());
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
-        match connection.post(&url, Some(payload)).await {
+        match connection
+            .post(&url, Some(self.stamp(self.redirect(spec.to_json()))))
+            .await
+        {
             Ok(response) => match response.into_json().await {
                 Ok(value) => Ok(ContainerCreate::Succeeded(value)),
                 Err(error) => Err(error),
@@ -63,9 +241,29 @@ impl DockerClient {
         }
     }
 
+    pub async fn containers_inspect(&self, id: &str) -> DockerResult<ContainerInspect> {
+        let url: String = format!("{}/containers/{id}/json", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ContainerInspect::Succeeded(Box::new(value))),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerInspect::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerInspect::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
     pub async fn containers_start(&self, id: &str) -> DockerResult<ContainerStart> {
-        let url: String = format!("/v1.42/containers/{id}/start");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+        let url: String = format!("{}/containers/{id}/start", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
         match connection.post(&url, None).await {
             Ok(response) => match response.into_bytes().await {
@@ -85,8 +283,8 @@ impl DockerClient {
     }
 
     pub async fn containers_stop(&self, id: &str) -> DockerResult<ContainerStop> {
-        let url: String = format!("/v1.42/containers/{id}/stop");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+        let url: String = format!("{}/containers/{id}/stop", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect_long_poll().await?;
 
         match connection.post(&url, None).await {
             Ok(response) => match response.into_bytes().await {
@@ -105,9 +303,183 @@ impl DockerClient {
         }
     }
 
-    pub async fn containers_wait(&self, id: &str) -> DockerResult<ContainerWait> {
-        let url: String = format!("/v1.42/containers/{id}/wait");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+    pub async fn containers_restart(&self, id: &str, timeout: Option<u32>) -> DockerResult<ContainerRestart> {
+        let url: String = match timeout {
+            None => format!("{}/containers/{id}/restart", self.prefix),
+            Some(timeout) => format!("{}/containers/{id}/restart?t={timeout}", self.prefix),
+        };
+
+        let connection: DockerConnection<Full<Bytes>> = self.connect_long_poll().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerRestart::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerRestart::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerRestart::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_top(&self, id: &str, ps_args: Option<&str>) -> DockerResult<ContainerTop> {
+        let url: String = match ps_args {
+            None => format!("{}/containers/{id}/top", self.prefix),
+            Some(value) => format!("{}/containers/{id}/top?ps_args={}", self.prefix, encode(value)),
+        };
+
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ContainerTop::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerTop::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerTop::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_resize(&self, id: &str, width: u16, height: u16) -> DockerResult<ContainerResize> {
+        let url: String = format!("{}/containers/{id}/resize?h={height}&w={width}", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerResize::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerResize::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerResize::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_update(&self, id: &str, spec: &ContainerUpdateSpec) -> DockerResult<ContainerUpdate> {
+        let url: String = format!("{}/containers/{id}/update", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(spec.to_json())).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ContainerUpdate::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerUpdate::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerUpdate::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_rename(&self, id: &str, name: &str) -> DockerResult<ContainerRename> {
+        let url: String = format!("{}/containers/{id}/rename?name={}", self.prefix, encode(name));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerRename::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerRename::NoSuchContainer(response.into_error().await?)),
+                    409 => Ok(ContainerRename::Conflict(response.into_error().await?)),
+                    500 => Ok(ContainerRename::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_pause(&self, id: &str) -> DockerResult<ContainerPause> {
+        let url: String = format!("{}/containers/{id}/pause", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerPause::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerPause::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerPause::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_unpause(&self, id: &str) -> DockerResult<ContainerUnpause> {
+        let url: String = format!("{}/containers/{id}/unpause", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerUnpause::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerUnpause::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerUnpause::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_kill(&self, id: &str, signal: Option<&str>) -> DockerResult<ContainerKill> {
+        let url: String = match signal {
+            None => format!("{}/containers/{id}/kill", self.prefix),
+            Some(signal) => format!("{}/containers/{id}/kill?signal={}", self.prefix, encode(signal)),
+        };
+
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ContainerKill::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerKill::NoSuchContainer(response.into_error().await?)),
+                    409 => Ok(ContainerKill::NotRunning(response.into_error().await?)),
+                    500 => Ok(ContainerKill::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_wait(&self, id: &str, condition: ContainerWaitCondition) -> DockerResult<ContainerWait> {
+        let url: String = format!("{}/containers/{id}/wait?condition={}", self.prefix, condition.as_str());
+        let connection: DockerConnection<Full<Bytes>> = self.connect_long_poll().await?;
 
         match connection.post(&url, None).await {
             Ok(response) => match response.into_json().await {
@@ -126,9 +498,9 @@ impl DockerClient {
         }
     }
 
-    pub async fn containers_remove(&self, id: &str) -> DockerResult<ContainerRemove> {
-        let url: String = format!("/v1.42/containers/{id}");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+    pub async fn containers_remove(&self, id: &str, force: bool) -> DockerResult<ContainerRemove> {
+        let url: String = format!("{}/containers/{id}?force={force}", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
         match connection.delete(&url).await {
             Ok(response) => match response.into_bytes().await {
@@ -148,9 +520,29 @@ impl DockerClient {
         }
     }
 
-    pub async fn containers_logs(&self, id: &str) -> DockerResult<ContainerLogs> {
-        let url: String = format!("/v1.42/containers/{id}/logs?stdout=true");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+    pub async fn containers_prune(&self, filters: &ContainerPruneFilters<'_>) -> DockerResult<ContainerPrune> {
+        let payload: Value = filters.to_json();
+        let url: String = format!("{}/containers/prune?filters={}", self.prefix, encode_json(&payload));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ContainerPrune::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(ContainerPrune::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_logs(&self, id: &str, options: &ContainerLogsOptions) -> DockerResult<ContainerLogs> {
+        let url: String = format!("{}/containers/{id}/logs?{}", self.prefix, options.to_query());
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
         match connection.get(&url).await {
             Ok(response) => Ok(ContainerLogs::Succeeded(ContainerLogsStream::from(response))),
@@ -166,8 +558,11 @@ impl DockerClient {
     }
 
     pub async fn containers_attach(&self, id: &str) -> DockerResult<ContainerAttach> {
-        let url: String = format!("/v1.42/containers/{id}/attach?logs=true&stream=true&stdout=true&stderr=true");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+        let url: String = format!(
+            "{}/containers/{id}/attach?logs=true&stream=true&stdout=true&stderr=true",
+            self.prefix
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
         match connection.post(&url, None).await {
             Ok(response) => Ok(ContainerAttach::Succeeded(ContainerLogsStream::from(response))),
@@ -183,9 +578,36 @@ impl DockerClient {
         }
     }
 
+    pub async fn containers_attach_stdin(&self, id: &str) -> DockerResult<ContainerAttachStdin> {
+        let url: String = format!(
+            "{}/containers/{id}/attach?stream=true&stdin=true&stdout=true&stderr=true",
+            self.prefix
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.upgrade(&url).await {
+            Ok(io) => {
+                let (reader, writer) = split(io);
+                let stdin: ContainerStdin = ContainerStdin::from(&url, writer);
+                let stream: ContainerAttachStream = ContainerAttachStream::from(&url, reader);
+
+                Ok(ContainerAttachStdin::Succeeded(stdin, stream))
+            }
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(ContainerAttachStdin::BadParameter(response.into_error().await?)),
+                    404 => Ok(ContainerAttachStdin::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerAttachStdin::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
     pub async fn container_upload(&self, id: &str, path: &str, archive: TarArchive) -> DockerResult<ContainerUpload> {
-        let url: String = format!("/v1.42/containers/{id}/archive?path={path}");
-        let connection: DockerConnection<TarBody> = DockerConnection::open(&self.socket).await?;
+        let url: String = format!("{}/containers/{id}/archive?path={path}", self.prefix);
+        let connection: DockerConnection<TarBody> = self.connect_streaming().await?;
 
         let stream: TarStream = archive.into_stream(64 * 1024);
         let data: TarBody = TarBody::from(stream);
@@ -208,11 +630,212 @@ impl DockerClient {
         }
     }
 
-    pub async fn images_create(&self) -> DockerResult<ImageCreate> {
-        let url: String = format!("/v1.42/images/create?fromImage=python:3.12");
-        let connection: DockerConnection<Full<Bytes>> = DockerConnection::open(&self.socket).await?;
+    pub async fn container_download(&self, id: &str, path: &str) -> DockerResult<ContainerDownload> {
+        let url: String = format!("{}/containers/{id}/archive?path={}", self.prefix, encode(path));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => Ok(ContainerDownload::Succeeded(ContainerDownloadStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(ContainerDownload::BadParameter(response.into_error().await?)),
+                    404 => Ok(ContainerDownload::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerDownload::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_commit(
+        &self,
+        id: &str,
+        repo: &str,
+        tag: &str,
+        changes: &[&str],
+    ) -> DockerResult<ContainerCommit> {
+        let mut url: String = format!(
+            "{}/commit?container={}&repo={}&tag={}",
+            self.prefix,
+            encode(id),
+            encode(repo),
+            encode(tag)
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        for change in changes {
+            url.push_str(&format!("&changes={}", encode(change)));
+        }
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ContainerCommit::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerCommit::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerCommit::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn containers_export(&self, id: &str) -> DockerResult<ContainerExport> {
+        let url: String = format!("{}/containers/{id}/export", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => Ok(ContainerExport::Succeeded(ContainerDownloadStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerExport::NoSuchContainer(response.into_error().await?)),
+                    500 => Ok(ContainerExport::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn container_archive(&self, id: &str, paths: &[&str]) -> DockerResult<ContainerArchive> {
+        let mut command: Vec<&str> = vec!["tar", "-cf", "-", "--"];
+        command.extend_from_slice(paths);
+
+        self.exec_stdout(id, command).await
+    }
+
+    pub async fn container_read_file(&self, id: &str, path: &str) -> DockerResult<ContainerReadFile> {
+        match self.exec_stdout(id, vec!["cat", "--", path]).await? {
+            ContainerArchive::Succeeded(stream) => Ok(ContainerReadFile::Succeeded(stream)),
+            ContainerArchive::NoSuchContainer(value) => Ok(ContainerReadFile::NoSuchContainer(value)),
+            ContainerArchive::Conflict(value) => Ok(ContainerReadFile::Conflict(value)),
+            ContainerArchive::ServerError(value) => Ok(ContainerReadFile::ServerError(value)),
+        }
+    }
+
+    // runs the command through exec and streams back only its stdout,
+    // the stream fails at its end when the command exits non-zero
+    async fn exec_stdout(&self, id: &str, command: Vec<&str>) -> DockerResult<ContainerArchive> {
+        let spec: ExecCreateSpec = ExecCreateSpec {
+            command,
+            ..ExecCreateSpec::default()
+        };
+        let exec: ExecCreateResponse = match self.exec_create(id, &spec).await? {
+            ExecCreate::Succeeded(value) => value,
+            ExecCreate::NoSuchContainer(value) => return Ok(ContainerArchive::NoSuchContainer(value)),
+            ExecCreate::Conflict(value) => return Ok(ContainerArchive::Conflict(value)),
+            ExecCreate::ServerError(value) => return Ok(ContainerArchive::ServerError(value)),
+        };
+
+        let url: String = format!("{}/exec/{}/start", self.prefix, exec.id);
+        let payload: Value = json!({"Detach": false, "Tty": false});
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(payload)).await {
+            Ok(response) => Ok(ContainerArchive::Succeeded(ContainerArchiveStream::from(
+                self.clone(),
+                exec.id,
+                response,
+            ))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ContainerArchive::NoSuchContainer(response.into_error().await?)),
+                    409 => Ok(ContainerArchive::Conflict(response.into_error().await?)),
+                    500 => Ok(ContainerArchive::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_list(&self, options: &ImageListOptions) -> DockerResult<ImageList> {
+        let url: String = format!("{}/images/json?{}", self.prefix, options.to_query());
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ImageList::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(ImageList::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_remove(&self, name: &str, force: bool, noprune: bool) -> DockerResult<ImageRemove> {
+        let url: String = format!(
+            "{}/images/{}?force={force}&noprune={noprune}",
+            self.prefix,
+            encode(name)
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.delete(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ImageRemove::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ImageRemove::NoSuchImage(response.into_error().await?)),
+                    409 => Ok(ImageRemove::Conflict(response.into_error().await?)),
+                    500 => Ok(ImageRemove::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_prune(&self, filters: &ImagePruneFilters<'_>) -> DockerResult<ImagePrune> {
+        let payload: Value = filters.to_json();
+        let url: String = format!("{}/images/prune?filters={}", self.prefix, encode_json(&payload));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
 
         match connection.post(&url, None).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ImagePrune::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(ImagePrune::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_create(
+        &self,
+        image: &ImageReference<'_>,
+        auth: Option<&RegistryAuth>,
+    ) -> DockerResult<ImageCreate> {
+        let mirrored: Option<String> = self.mirror.as_ref().and_then(|mirror| image.mirror(mirror));
+        let image: ImageReference = match &mirrored {
+            Some(name) => ImageReference { name, ..*image },
+            None => *image,
+        };
+
+        let url: String = format!("{}/images/create?{}", self.prefix, image.to_query());
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+        let auth: String = RegistryAuth::encode(auth);
+
+        match connection
+            .post_with_headers(&url, None, &[("X-Registry-Auth", &auth)])
+            .await
+        {
             Ok(response) => Ok(ImageCreate::Succeeded(ImageCreateStream::from(response))),
             Err(error) => match error {
                 DockerError::StatusFailed(url, status, response) => match status.as_u16() {
@@ -224,4 +847,268 @@ impl DockerClient {
             },
         }
     }
+
+    pub async fn images_build(&self, spec: &ImageBuildSpec<'_>, context: TarArchive) -> DockerResult<ImageBuild> {
+        let url: String = format!("{}/build?{}", self.prefix, spec.to_query());
+        let connection: DockerConnection<TarBody> = self.connect_streaming().await?;
+
+        let stream: TarStream = context.into_stream(64 * 1024);
+        let data: TarBody = TarBody::from(stream);
+        let config: String = RegistryAuth::encode_config(&spec.registry_auths);
+
+        match connection
+            .post_archive(&url, data, &[("X-Registry-Config", &config)])
+            .await
+        {
+            Ok(response) => Ok(ImageBuild::Succeeded(ImageBuildStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(ImageBuild::BadParameter(response.into_error().await?)),
+                    500 => Ok(ImageBuild::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_get(&self, name: &str) -> DockerResult<ImageGet> {
+        let url: String = format!("{}/images/{name}/get", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => Ok(ImageGet::Succeeded(ContainerDownloadStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ImageGet::NoSuchImage(response.into_error().await?)),
+                    500 => Ok(ImageGet::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_load<S>(&self, data: S) -> DockerResult<ImageLoad>
+    where
+        S: Stream<Item = DockerResult<Bytes>> + Send + 'static,
+    {
+        let url: String = format!("{}/images/load?quiet=false", self.prefix);
+        let connection: DockerConnection<StreamBody> = self.connect_streaming().await?;
+
+        match connection.post_archive(&url, StreamBody::from(data), &[]).await {
+            Ok(response) => Ok(ImageLoad::Succeeded(ImageBuildStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(ImageLoad::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_push(&self, name: &str, tag: &str, auth: Option<&RegistryAuth>) -> DockerResult<ImagePush> {
+        let url: String = format!("{}/images/{name}/push?tag={}", self.prefix, encode(tag));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+        let auth: String = RegistryAuth::encode(auth);
+
+        match connection
+            .post_with_headers(&url, None, &[("X-Registry-Auth", &auth)])
+            .await
+        {
+            Ok(response) => Ok(ImagePush::Succeeded(ImageCreateStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ImagePush::NoSuchImage(response.into_error().await?)),
+                    500 => Ok(ImagePush::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn exec_create(&self, id: &str, spec: &ExecCreateSpec<'_>) -> DockerResult<ExecCreate> {
+        let url: String = format!("{}/containers/{id}/exec", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(spec.to_json())).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ExecCreate::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ExecCreate::NoSuchContainer(response.into_error().await?)),
+                    409 => Ok(ExecCreate::Conflict(response.into_error().await?)),
+                    500 => Ok(ExecCreate::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn exec_start(&self, id: &str) -> DockerResult<ExecStart> {
+        let url: String = format!("{}/exec/{id}/start", self.prefix);
+        let payload: Value = json!({"Detach": true, "Tty": false});
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(payload)).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ExecStart::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ExecStart::NoSuchInstance(response.into_error().await?)),
+                    409 => Ok(ExecStart::Conflict(response.into_error().await?)),
+                    500 => Ok(ExecStart::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn exec_attach(&self, id: &str) -> DockerResult<ExecAttach> {
+        let url: String = format!("{}/exec/{id}/start", self.prefix);
+        let payload: Value = json!({"Detach": false, "Tty": false});
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(payload)).await {
+            Ok(response) => Ok(ExecAttach::Succeeded(ContainerLogsStream::from(response))),
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ExecAttach::NoSuchInstance(response.into_error().await?)),
+                    409 => Ok(ExecAttach::Conflict(response.into_error().await?)),
+                    500 => Ok(ExecAttach::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn exec_inspect(&self, id: &str) -> DockerResult<ExecInspect> {
+        let url: String = format!("{}/exec/{id}/json", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(ExecInspect::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(ExecInspect::NoSuchInstance(response.into_error().await?)),
+                    500 => Ok(ExecInspect::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn exec_resize(&self, id: &str, width: u16, height: u16) -> DockerResult<ExecResize> {
+        let url: String = format!("{}/exec/{id}/resize?h={height}&w={width}", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(ExecResize::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(ExecResize::BadParameter(response.into_error().await?)),
+                    404 => Ok(ExecResize::NoSuchInstance(response.into_error().await?)),
+                    500 => Ok(ExecResize::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn images_pull<F>(
+        &self,
+        image: &ImageReference<'_>,
+        auth: Option<&RegistryAuth>,
+        retry: &ImagePullRetry,
+        mut progress: F,
+    ) -> DockerResult<ImagePull>
+    where
+        F: FnMut(&ImageCreateStreamLine),
+    {
+        let mut attempt: u32 = 0;
+
+        loop {
+            let last: bool = attempt + 1 >= retry.attempts;
+            let outcome: DockerResult<ImagePull> = match self.images_create(image, auth).await {
+                Err(error) => Err(error),
+                Ok(ImageCreate::NoReadAccess(value)) => return Ok(ImagePull::NoReadAccess(value)),
+                Ok(ImageCreate::ServerError(value)) => Ok(ImagePull::ServerError(value)),
+                Ok(ImageCreate::Succeeded(mut stream)) => {
+                    let mut digest: Option<String> = None;
+                    let mut outcome: Option<DockerResult<ImagePull>> = None;
+
+                    while let Some(item) = stream.next().await {
+                        match item {
+                            Err(error) => outcome = Some(Err(error)),
+                            // the daemon gave up on the pull, nothing else follows
+                            Ok(ImageCreateStreamLine::Error(value)) => {
+                                outcome = Some(Ok(ImagePull::Failed(value)));
+                                break;
+                            }
+                            Ok(line) => {
+                                if let Some(value) = line.digest() {
+                                    digest = Some(value.to_owned());
+                                }
+
+                                progress(&line)
+                            }
+                        }
+                    }
+
+                    match outcome {
+                        Some(outcome) => outcome,
+                        None => Ok(ImagePull::Succeeded(digest)),
+                    }
+                }
+            };
+
+            // only broken connections and daemon failures are retried,
+            // already downloaded layers are kept by the daemon,
+            // so the next attempt continues from where it failed
+            match outcome {
+                Err(ref error) if error.is_transport() && !last => sleep(retry.delay(attempt)).await,
+                Ok(ImagePull::ServerError(_)) if !last => sleep(retry.delay(attempt)).await,
+                outcome => return outcome,
+            }
+
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_default_labels_below_spec_and_owner() {
+        let client: DockerClient = DockerClient::open("/var/run/docker.sock")
+            .with_label("team", "data")
+            .with_label("environment", "prod")
+            .with_label("etl0.run", "default")
+            .with_owner(DockerOwner::new("pipeline", "task", "run"));
+
+        let payload: Value = client.stamp(json!({"Labels": {"environment": "test"}}));
+
+        assert_eq!(payload["Labels"]["team"], "data");
+        assert_eq!(payload["Labels"]["environment"], "test");
+        assert_eq!(payload["Labels"]["etl0.run"], "run");
+    }
 }
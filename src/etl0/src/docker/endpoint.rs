@@ -0,0 +1,147 @@
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use super::error::{DockerError, DockerResult};
+use super::tls::DockerTls;
+
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const DOCKER_TCP_PORT: u16 = 2375;
+const DOCKER_TLS_PORT: u16 = 2376;
+
+#[derive(Debug, Clone)]
+pub enum DockerEndpoint {
+    Unix(String),
+    Tcp(String),
+    Tls(String, DockerTls),
+    Ssh(String, Option<u16>),
+}
+
+impl DockerEndpoint {
+    pub fn unix(socket: &str) -> Self {
+        DockerEndpoint::Unix(socket.to_owned())
+    }
+
+    // accepts the same forms as the docker cli: unix:///path, tcp://host[:port],
+    // ssh://[user@]host[:port] or a plain path to the unix socket
+    pub fn parse(value: &str) -> DockerResult<Self> {
+        if let Some(path) = value.strip_prefix("unix://") {
+            return match path.is_empty() {
+                true => DockerError::raise_endpoint_invalid(value, "missing socket path"),
+                false => Ok(DockerEndpoint::unix(path)),
+            };
+        }
+
+        if let Some(address) = value.strip_prefix("tcp://") {
+            let address: &str = address.trim_end_matches('/');
+
+            return match address.rsplit_once(':') {
+                _ if address.is_empty() => DockerError::raise_endpoint_invalid(value, "missing host"),
+                Some((_, port)) if port.parse::<u16>().is_ok() => Ok(DockerEndpoint::Tcp(address.to_owned())),
+                Some((_, port)) if !address.ends_with(']') => {
+                    DockerError::raise_endpoint_invalid(value, &format!("invalid port '{port}'"))
+                }
+                // the default port is chosen when connecting, after
+                // the endpoint may have been secured with TLS
+                _ => Ok(DockerEndpoint::Tcp(address.to_owned())),
+            };
+        }
+
+        if let Some(destination) = value.strip_prefix("ssh://") {
+            let destination: &str = destination.trim_end_matches('/');
+
+            return match destination.rsplit_once(':') {
+                _ if destination.is_empty() => DockerError::raise_endpoint_invalid(value, "missing host"),
+                Some((host, port)) => match port.parse::<u16>() {
+                    Err(_) => DockerError::raise_endpoint_invalid(value, &format!("invalid port '{port}'")),
+                    Ok(port) => Ok(DockerEndpoint::Ssh(host.to_owned(), Some(port))),
+                },
+                None => Ok(DockerEndpoint::Ssh(destination.to_owned(), None)),
+            };
+        }
+
+        match value.starts_with('/') {
+            true => Ok(DockerEndpoint::unix(value)),
+            false => DockerError::raise_endpoint_invalid(value, "unsupported scheme"),
+        }
+    }
+
+    pub fn secure(self, tls: DockerTls) -> DockerResult<Self> {
+        match self {
+            DockerEndpoint::Tcp(address) | DockerEndpoint::Tls(address, _) => Ok(DockerEndpoint::Tls(address, tls)),
+            endpoint => DockerError::raise_endpoint_invalid(&endpoint.to_string(), "TLS requires a tcp endpoint"),
+        }
+    }
+
+    // appends the default port of plain or TLS connections
+    // to addresses parsed from tcp://host without any port
+    pub(crate) fn resolve(address: &str, tls: bool) -> String {
+        let port: u16 = match tls {
+            true => DOCKER_TLS_PORT,
+            false => DOCKER_TCP_PORT,
+        };
+
+        match address.rsplit_once(':') {
+            Some((_, value)) if !address.ends_with(']') && value.parse::<u16>().is_ok() => address.to_owned(),
+            _ => format!("{address}:{port}"),
+        }
+    }
+
+    // honors DOCKER_HOST and falls back to the local socket, like the
+    // docker cli any non-empty DOCKER_TLS_VERIFY turns verified TLS on
+    pub fn from_env() -> DockerResult<Self> {
+        let endpoint: Self = match std::env::var("DOCKER_HOST") {
+            Ok(value) if !value.is_empty() => Self::parse(&value)?,
+            _ => DockerEndpoint::unix(DOCKER_SOCKET),
+        };
+
+        match std::env::var("DOCKER_TLS_VERIFY") {
+            Ok(value) if !value.is_empty() => (),
+            _ => return Ok(endpoint),
+        }
+
+        let path: PathBuf = match std::env::var_os("DOCKER_CERT_PATH") {
+            Some(value) if !value.is_empty() => PathBuf::from(value),
+            _ => match std::env::var_os("HOME") {
+                Some(value) => PathBuf::from(value).join(".docker"),
+                None => {
+                    return DockerError::raise_endpoint_invalid(&endpoint.to_string(), "DOCKER_CERT_PATH is not set")
+                }
+            },
+        };
+
+        endpoint.secure(DockerTls::load(path)?)
+    }
+}
+
+impl Display for DockerEndpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DockerEndpoint::Unix(path) => write!(f, "unix://{path}"),
+            DockerEndpoint::Tcp(address) => write!(f, "tcp://{address}"),
+            DockerEndpoint::Tls(address, _) => write!(f, "tcp://{address} (tls)"),
+            DockerEndpoint::Ssh(destination, None) => write!(f, "ssh://{destination}"),
+            DockerEndpoint::Ssh(destination, Some(port)) => write!(f, "ssh://{destination}:{port}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defers_default_port_until_tls_is_known() {
+        let address: String = match DockerEndpoint::parse("tcp://docker.internal/").unwrap() {
+            DockerEndpoint::Tcp(address) => address,
+            other => panic!("unexpected endpoint {other}"),
+        };
+
+        assert_eq!(DockerEndpoint::resolve(&address, false), "docker.internal:2375");
+        assert_eq!(DockerEndpoint::resolve(&address, true), "docker.internal:2376");
+        assert_eq!(DockerEndpoint::resolve("[::1]", true), "[::1]:2376");
+        assert_eq!(
+            DockerEndpoint::resolve("docker.internal:2375", true),
+            "docker.internal:2375"
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use std::path::Path;
+use std::time::Duration;
+
 use hyper::body::{Bytes, Frame};
 use hyper::StatusCode;
 use thiserror::Error;
@@ -7,49 +10,173 @@ use crate::tar::TarError;
 
 #[derive(Debug, Error)]
 pub enum DockerError {
-    #[error("Cannot connected to '{0}', because '{1}'")]
-    UnixSocketConnect(String, std::io::Error),
+    #[error("[E001] Cannot connected to '{0}'")]
+    UnixSocketConnect(String, #[source] std::io::Error),
+
+    #[error("[E002] Cannot perform handshake to '{0}'")]
+    HandshakeFailed(String, #[source] hyper::Error),
+
+    #[error("[E003] Cannot build HTTP request to '{0}'")]
+    BuilderFailed(String, #[source] hyper::http::Error),
+
+    #[error("[E004] Cannot clean HTTP connection to '{0}'")]
+    ConnectionFailed(String, #[source] hyper::Error),
+
+    #[error("[E005] Cannot join HTTP connection to '{0}'")]
+    TokioFailed(String, #[source] tokio::task::JoinError),
+
+    #[error("[E006] Cannot send HTTP request to '{0}'")]
+    RequestFailed(String, #[source] hyper::Error),
+
+    #[error("[E007] Cannot accept HTTP status code from '{0}', because '{1}'")]
+    StatusFailed(String, hyper::http::StatusCode, Box<DockerResponse>),
+
+    #[error("[E008] Cannot handle HTTP frame from '{0}'")]
+    HttpFrameFailed(String, #[source] hyper::Error),
+
+    #[error("[E009] Cannot recognize HTTP frame from '{0}'")]
+    HttpFrameUnrecognized(String, Box<Frame<Bytes>>),
+
+    #[error("[E010] Cannot receive HTTP response from '{0}'")]
+    ResponseFailed(String, #[source] hyper::Error),
+
+    #[error("[E011] Cannot upgrade HTTP connection to '{0}'")]
+    UpgradeFailed(String, #[source] hyper::Error),
+
+    #[error("[E012] Cannot use upgraded HTTP connection to '{0}'")]
+    UpgradedIoFailed(String, #[source] std::io::Error),
+
+    #[error("[E013] Cannot receive HTTP response from '{0}' within {1:?}")]
+    ResponseTimeout(String, Duration),
+
+    #[error("[E014] Cannot receive HTTP response from '{0}' larger than {1} bytes")]
+    ResponseTooLarge(String, usize),
+
+    #[error("[E015] Cannot deserialize JSON payload from '{0:?}'")]
+    DeserializationFailed(Option<hyper::http::StatusCode>, #[source] serde_json::Error, Bytes),
+
+    #[error("[E016] Cannot parse utf8 text")]
+    Utf8ParsingFailed(#[source] std::str::Utf8Error),
+
+    #[error("[E017] Cannot process tar archive")]
+    OutgoingArchiveFailed(#[source] TarError),
+
+    #[error("[E018] Cannot extract tar archive")]
+    IncomingArchiveFailed(#[source] TarError),
 
-    #[error("Cannot perform handshake to '{0}', because '{1}'")]
-    HandshakeFailed(String, hyper::Error),
+    #[error("[E019] Cannot read registry config '{0}'")]
+    RegistryConfigFailed(String, #[source] std::io::Error),
 
-    #[error("Cannot build HTTP request to '{0}', because '{1}'")]
-    BuilderFailed(String, hyper::http::Error),
+    #[error("[E020] Cannot parse registry config '{0}', because '{1}'")]
+    RegistryConfigInvalid(String, String),
 
-    #[error("Cannot clean HTTP connection to '{0}', because '{1}'")]
-    ConnectionFailed(String, hyper::Error),
+    #[error("[E021] Cannot agree on API version with daemon supporting up to '{0}'")]
+    UnsupportedApiVersion(String, Option<String>),
 
-    #[error("Cannot join HTTP connection to '{0}', because '{1}'")]
-    TokioFailed(String, tokio::task::JoinError),
+    #[error("[E022] Cannot find docker socket '{0}', is the daemon running?")]
+    SocketNotFound(String),
 
-    #[error("Cannot send HTTP request to '{0}', because '{1}'")]
-    RequestFailed(String, hyper::Error),
+    #[error("[E023] Cannot access docker socket '{0}', add the user to the docker group or adjust its permissions")]
+    SocketPermissionDenied(String),
 
-    #[error("Cannot accept HTTP status code from '{0}', because '{1}'")]
-    StatusFailed(String, hyper::http::StatusCode, DockerResponse),
+    #[error("[E024] Cannot reach daemon behind socket '{0}', it exists but nothing is listening")]
+    SocketRefused(String),
 
-    #[error("Cannot handle HTTP frame from '{0}', because '{1}'")]
-    HttpFrameFailed(String, hyper::Error),
+    #[error("[E025] Cannot confirm daemon behind socket '{0}' is healthy, it answered '{1}'")]
+    PingFailed(String, String),
 
-    #[error("Cannot recognize HTTP frame from '{0}'")]
-    HttpFrameUnrecognized(String, Frame<Bytes>),
+    #[error("[E026] Cannot use docker endpoint '{0}', because {1}")]
+    EndpointInvalid(String, String),
 
-    #[error("Cannot receive HTTP response from '{0}', because '{1}'")]
-    ResponseFailed(String, hyper::Error),
+    #[error("[E027] Cannot connect to '{0}'")]
+    TcpConnect(String, #[source] std::io::Error),
 
-    #[error("Cannot deserialize JSON payload from '{0:?}', because '{1}'")]
-    DeserializationFailed(Option<hyper::http::StatusCode>, serde_json::Error, Bytes),
+    #[error("[E028] Cannot load TLS configuration from '{0}', because {1}")]
+    TlsConfigFailed(String, String),
 
-    #[error("Cannot parse utf8 text, because '{0}'")]
-    Utf8ParsingFailed(std::str::Utf8Error),
+    #[error("[E029] Cannot negotiate TLS with '{0}'")]
+    TlsHandshakeFailed(String, #[source] std::io::Error),
 
-    #[error("Cannot process tar archive, because '{0}'")]
-    OutgoingArchiveFailed(TarError),
+    #[error("[E030] Cannot start ssh tunnel to '{0}'")]
+    SshSpawnFailed(String, #[source] std::io::Error),
+
+    #[error("[E031] Cannot pull image, because '{0}'")]
+    PullFailed(String),
+
+    #[error("[E032] Cannot clean up orphaned {0}, because '{1}'")]
+    CleanupFailed(String, String),
+
+    #[error("[E033] Cannot complete exec '{0}', it exited with code {1} printing '{2}'")]
+    ExecFailed(String, i64, String),
+
+    #[error("[E034] Cannot verify exec '{0}' completed, because '{1}'")]
+    ExecUnverified(String, String),
+
+    #[error("[E035] Cannot use registry mirror '{0}', because {1}")]
+    MirrorInvalid(String, String),
 }
 
 pub type DockerResult<T> = Result<T, DockerError>;
 
 impl DockerError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            DockerError::UnixSocketConnect(..) => "E001",
+            DockerError::HandshakeFailed(..) => "E002",
+            DockerError::BuilderFailed(..) => "E003",
+            DockerError::ConnectionFailed(..) => "E004",
+            DockerError::TokioFailed(..) => "E005",
+            DockerError::RequestFailed(..) => "E006",
+            DockerError::StatusFailed(..) => "E007",
+            DockerError::HttpFrameFailed(..) => "E008",
+            DockerError::HttpFrameUnrecognized(..) => "E009",
+            DockerError::ResponseFailed(..) => "E010",
+            DockerError::UpgradeFailed(..) => "E011",
+            DockerError::UpgradedIoFailed(..) => "E012",
+            DockerError::ResponseTimeout(..) => "E013",
+            DockerError::ResponseTooLarge(..) => "E014",
+            DockerError::DeserializationFailed(..) => "E015",
+            DockerError::Utf8ParsingFailed(..) => "E016",
+            DockerError::OutgoingArchiveFailed(..) => "E017",
+            DockerError::IncomingArchiveFailed(..) => "E018",
+            DockerError::RegistryConfigFailed(..) => "E019",
+            DockerError::RegistryConfigInvalid(..) => "E020",
+            DockerError::UnsupportedApiVersion(..) => "E021",
+            DockerError::SocketNotFound(..) => "E022",
+            DockerError::SocketPermissionDenied(..) => "E023",
+            DockerError::SocketRefused(..) => "E024",
+            DockerError::PingFailed(..) => "E025",
+            DockerError::EndpointInvalid(..) => "E026",
+            DockerError::TcpConnect(..) => "E027",
+            DockerError::TlsConfigFailed(..) => "E028",
+            DockerError::TlsHandshakeFailed(..) => "E029",
+            DockerError::SshSpawnFailed(..) => "E030",
+            DockerError::PullFailed(..) => "E031",
+            DockerError::CleanupFailed(..) => "E032",
+            DockerError::ExecFailed(..) => "E033",
+            DockerError::ExecUnverified(..) => "E034",
+            DockerError::MirrorInvalid(..) => "E035",
+        }
+    }
+
+    // failures of the connection itself, which may succeed when
+    // the same request is sent again, unlike rejected requests
+    pub fn is_transport(&self) -> bool {
+        matches!(
+            self,
+            DockerError::UnixSocketConnect(..)
+                | DockerError::HandshakeFailed(..)
+                | DockerError::ConnectionFailed(..)
+                | DockerError::RequestFailed(..)
+                | DockerError::HttpFrameFailed(..)
+                | DockerError::ResponseFailed(..)
+                | DockerError::ResponseTimeout(..)
+                | DockerError::SocketRefused(..)
+                | DockerError::TcpConnect(..)
+                | DockerError::TlsHandshakeFailed(..)
+        )
+    }
+
     pub(crate) fn raise_unix_socket_connect<T>(socket: &str, error: std::io::Error) -> DockerResult<T> {
         Err(Self::UnixSocketConnect(socket.to_owned(), error))
     }
@@ -75,7 +202,7 @@ impl DockerError {
     }
 
     pub(crate) fn raise_status_failed<T>(status: hyper::http::StatusCode, response: DockerResponse) -> DockerResult<T> {
-        Err(Self::StatusFailed(response.url.to_owned(), status, response))
+        Err(Self::StatusFailed(response.url.to_owned(), status, Box::new(response)))
     }
 
     pub(crate) fn raise_http_frame_failed<T>(url: &str, error: hyper::Error) -> DockerResult<T> {
@@ -83,13 +210,29 @@ impl DockerError {
     }
 
     pub(crate) fn raise_http_frame_unrecognized<T>(url: &str, frame: Frame<Bytes>) -> DockerResult<T> {
-        Err(Self::HttpFrameUnrecognized(url.to_owned(), frame))
+        Err(Self::HttpFrameUnrecognized(url.to_owned(), Box::new(frame)))
     }
 
     pub(crate) fn raise_response_failed<T>(url: &str, error: hyper::Error) -> DockerResult<T> {
         Err(Self::ResponseFailed(url.to_owned(), error))
     }
 
+    pub(crate) fn raise_upgrade_failed<T>(url: &str, error: hyper::Error) -> DockerResult<T> {
+        Err(Self::UpgradeFailed(url.to_owned(), error))
+    }
+
+    pub(crate) fn raise_upgraded_io_failed<T>(url: &str, error: std::io::Error) -> DockerResult<T> {
+        Err(Self::UpgradedIoFailed(url.to_owned(), error))
+    }
+
+    pub(crate) fn raise_response_timeout<T>(url: &str, timeout: Duration) -> DockerResult<T> {
+        Err(Self::ResponseTimeout(url.to_owned(), timeout))
+    }
+
+    pub(crate) fn raise_response_too_large<T>(url: &str, size: usize) -> DockerResult<T> {
+        Err(Self::ResponseTooLarge(url.to_owned(), size))
+    }
+
     pub(crate) fn raise_deserialization_failed<T>(
         status: Option<StatusCode>,
         error: serde_json::Error,
@@ -105,4 +248,83 @@ impl DockerError {
     pub(crate) fn raise_outgoing_archive_failed<T>(error: TarError) -> DockerResult<T> {
         Err(Self::OutgoingArchiveFailed(error))
     }
+
+    pub(crate) fn raise_incoming_archive_failed<T>(error: TarError) -> DockerResult<T> {
+        Err(Self::IncomingArchiveFailed(error))
+    }
+
+    pub(crate) fn raise_registry_config_failed<T>(path: &str, error: std::io::Error) -> DockerResult<T> {
+        Err(Self::RegistryConfigFailed(path.to_owned(), error))
+    }
+
+    pub(crate) fn raise_registry_config_invalid<T>(path: &str, reason: String) -> DockerResult<T> {
+        Err(Self::RegistryConfigInvalid(path.to_owned(), reason))
+    }
+
+    pub(crate) fn raise_unsupported_api_version<T>(maximum: &str, minimum: Option<&str>) -> DockerResult<T> {
+        Err(Self::UnsupportedApiVersion(
+            maximum.to_owned(),
+            minimum.map(str::to_owned),
+        ))
+    }
+
+    pub(crate) fn raise_socket_not_found<T>(socket: &str) -> DockerResult<T> {
+        Err(Self::SocketNotFound(socket.to_owned()))
+    }
+
+    pub(crate) fn raise_socket_permission_denied<T>(socket: &str) -> DockerResult<T> {
+        Err(Self::SocketPermissionDenied(socket.to_owned()))
+    }
+
+    pub(crate) fn raise_socket_refused<T>(socket: &str) -> DockerResult<T> {
+        Err(Self::SocketRefused(socket.to_owned()))
+    }
+
+    pub(crate) fn raise_ping_failed<T>(socket: &str, answer: String) -> DockerResult<T> {
+        Err(Self::PingFailed(socket.to_owned(), answer))
+    }
+
+    pub(crate) fn raise_endpoint_invalid<T>(endpoint: &str, reason: &str) -> DockerResult<T> {
+        Err(Self::EndpointInvalid(endpoint.to_owned(), reason.to_owned()))
+    }
+
+    pub(crate) fn raise_tcp_connect<T>(address: &str, error: std::io::Error) -> DockerResult<T> {
+        Err(Self::TcpConnect(address.to_owned(), error))
+    }
+
+    pub(crate) fn raise_tls_config_failed<T>(path: &Path, reason: String) -> DockerResult<T> {
+        Err(Self::TlsConfigFailed(path.to_string_lossy().to_string(), reason))
+    }
+
+    pub(crate) fn raise_tls_handshake_failed<T>(address: &str, error: std::io::Error) -> DockerResult<T> {
+        Err(Self::TlsHandshakeFailed(address.to_owned(), error))
+    }
+
+    pub(crate) fn raise_ssh_spawn_failed<T>(destination: &str, error: std::io::Error) -> DockerResult<T> {
+        Err(Self::SshSpawnFailed(destination.to_owned(), error))
+    }
+
+    pub(crate) fn raise_pull_failed<T>(message: &str) -> DockerResult<T> {
+        Err(Self::PullFailed(message.to_owned()))
+    }
+
+    pub(crate) fn raise_cleanup_failed<T>(resource: &str, message: String) -> DockerResult<T> {
+        Err(Self::CleanupFailed(resource.to_owned(), message))
+    }
+
+    pub(crate) fn raise_exec_failed<T>(id: &str, code: i64, stderr: &[u8]) -> DockerResult<T> {
+        Err(Self::ExecFailed(
+            id.to_owned(),
+            code,
+            String::from_utf8_lossy(stderr).trim().to_owned(),
+        ))
+    }
+
+    pub(crate) fn raise_exec_unverified<T>(id: &str, message: String) -> DockerResult<T> {
+        Err(Self::ExecUnverified(id.to_owned(), message))
+    }
+
+    pub(crate) fn raise_mirror_invalid<T>(rule: &str, reason: &str) -> DockerResult<T> {
+        Err(Self::MirrorInvalid(rule.to_owned(), reason.to_owned()))
+    }
 }
@@ -1,46 +1,167 @@
 use std::path::Path;
+use std::sync::{Arc, Mutex};
 
-use hyper::body::{Body, Bytes, Incoming};
+use hyper::body::{Body, Bytes, Frame, Incoming};
 use hyper::client::conn::http1::{handshake, SendRequest};
+use hyper::upgrade::{self, Upgraded};
 use hyper::{Request, Response, StatusCode};
 
 use http_body_util::{BodyExt, Full};
 use hyper_util::rt::TokioIo;
 use serde_json::{from_slice, Value};
 
-use tokio::net::UnixStream;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpStream, UnixStream};
 use tokio::spawn;
 use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_rustls::rustls::pki_types::ServerName;
 
+use super::endpoint::DockerEndpoint;
 use super::error::{DockerError, DockerResult};
-use super::types::ErrorResponse;
+use super::ssh::SshStream;
+use super::tls::DockerTls;
+use super::types::{DockerResponseLimits, ErrorResponse};
+
+type DockerConnectionTask = JoinHandle<Result<(), hyper::Error>>;
+type DockerIdleConnection = (SendRequest<Full<Bytes>>, DockerConnectionTask);
+
+// keeps idle keep-alive connections around, so consecutive requests
+// of the same client do not pay for a new socket and handshake each
+#[derive(Debug, Clone)]
+pub struct DockerPool {
+    idle: Arc<Mutex<Vec<DockerIdleConnection>>>,
+    capacity: usize,
+}
+
+impl DockerPool {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            idle: Arc::new(Mutex::new(Vec::with_capacity(capacity))),
+            capacity: capacity,
+        }
+    }
+
+    fn take(&self) -> Option<DockerIdleConnection> {
+        let mut idle = match self.idle.lock() {
+            Err(_) => return None,
+            Ok(value) => value,
+        };
+
+        while let Some((sender, connection)) = idle.pop() {
+            if !sender.is_closed() && !connection.is_finished() {
+                return Some((sender, connection));
+            }
+        }
+
+        None
+    }
+
+    fn release(&self, sender: SendRequest<Full<Bytes>>, connection: DockerConnectionTask) {
+        if sender.is_closed() || connection.is_finished() {
+            return;
+        }
+
+        if let Ok(mut idle) = self.idle.lock() {
+            if idle.len() < self.capacity {
+                idle.push((sender, connection));
+            }
+        }
+    }
+}
 
 #[derive(Debug)]
-pub(crate) struct DockerResponse {
+pub(crate) struct DockerReuse {
+    sender: SendRequest<Full<Bytes>>,
+    pool: DockerPool,
+}
+
+#[derive(Debug)]
+pub struct DockerResponse {
     pub(crate) url: String,
     pub(crate) inner: Response<Incoming>,
-    pub(crate) connection: JoinHandle<Result<(), hyper::Error>>,
+    pub(crate) connection: DockerConnectionTask,
+    pub(crate) limits: DockerResponseLimits,
+    pub(crate) reuse: Option<DockerReuse>,
 }
 
 impl DockerResponse {
-    fn new(url: &str, response: Response<Incoming>, connection: JoinHandle<Result<(), hyper::Error>>) -> Self {
+    fn new(
+        url: &str,
+        response: Response<Incoming>,
+        connection: DockerConnectionTask,
+        limits: DockerResponseLimits,
+        reuse: Option<DockerReuse>,
+    ) -> Self {
         Self {
             url: url.to_owned(),
             inner: response,
             connection: connection,
+            limits: limits,
+            reuse: reuse,
         }
     }
 
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
     pub fn status(&self) -> StatusCode {
         self.inner.status()
     }
 
+    async fn collect(url: &str, mut body: Incoming, max_size: Option<usize>) -> DockerResult<Bytes> {
+        let mut data: Vec<u8> = Vec::new();
+
+        while let Some(frame) = body.frame().await {
+            let frame: Frame<Bytes> = match frame {
+                Err(error) => return DockerError::raise_response_failed(url, error),
+                Ok(value) => value,
+            };
+
+            if let Ok(chunk) = frame.into_data() {
+                if let Some(max_size) = max_size {
+                    if data.len() + chunk.len() > max_size {
+                        return DockerError::raise_response_too_large(url, max_size);
+                    }
+                }
+
+                data.extend_from_slice(&chunk);
+            }
+        }
+
+        Ok(Bytes::from(data))
+    }
+
     pub async fn into_bytes(self) -> DockerResult<Bytes> {
-        let data: Bytes = match self.inner.collect().await {
-            Err(error) => return DockerError::raise_response_failed(&self.url, error),
-            Ok(value) => value.to_bytes(),
+        let body: Incoming = self.inner.into_body();
+        let task = Self::collect(&self.url, body, self.limits.max_size);
+
+        let data: DockerResult<Bytes> = match self.limits.timeout {
+            None => task.await,
+            Some(value) => match timeout(value, task).await {
+                Err(_) => DockerError::raise_response_timeout(&self.url, value),
+                Ok(data) => data,
+            },
         };
 
+        // a broken response leaves the connection in unknown
+        // state and there is no reason to wait for it
+        let data: Bytes = match data {
+            Err(error) => {
+                self.connection.abort();
+                return Err(error);
+            }
+            Ok(value) => value,
+        };
+
+        // the body was read completely, so the connection is idle
+        // again and may serve the next request instead of closing
+        if let Some(reuse) = self.reuse {
+            reuse.pool.release(reuse.sender, self.connection);
+            return Ok(data);
+        }
+
         match self.connection.await {
             Err(error) => return DockerError::raise_tokio_failed(&self.url, error),
             Ok(Err(error)) => return DockerError::raise_connection_failed(&self.url, error),
@@ -73,7 +194,9 @@ where
     T: Body,
 {
     sender: SendRequest<T>,
-    connection: JoinHandle<Result<(), hyper::Error>>,
+    connection: DockerConnectionTask,
+    limits: DockerResponseLimits,
+    pool: Option<DockerPool>,
 }
 
 impl<T> DockerConnection<T>
@@ -82,17 +205,51 @@ where
     T::Data: Send,
     T::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
 {
-    pub async fn open(socket: &str) -> DockerResult<Self> {
-        let stream: TokioIo<UnixStream> = match UnixStream::connect(Path::new(socket)).await {
-            Err(error) => return DockerError::raise_unix_socket_connect(socket, error),
-            Ok(stream) => TokioIo::new(stream),
-        };
+    pub async fn open(endpoint: &DockerEndpoint, limits: DockerResponseLimits) -> DockerResult<Self> {
+        match endpoint {
+            DockerEndpoint::Unix(socket) => match UnixStream::connect(Path::new(socket)).await {
+                Err(error) => DockerError::raise_unix_socket_connect(socket, error),
+                Ok(stream) => Self::handshake(socket, stream, limits).await,
+            },
+            DockerEndpoint::Tcp(address) => {
+                let address: String = DockerEndpoint::resolve(address, false);
+
+                match TcpStream::connect(&address).await {
+                    Err(error) => DockerError::raise_tcp_connect(&address, error),
+                    Ok(stream) => Self::handshake(&address, stream, limits).await,
+                }
+            }
+            DockerEndpoint::Tls(address, tls) => {
+                let address: String = DockerEndpoint::resolve(address, true);
+                let name: ServerName<'static> = DockerTls::server_name(&address)?;
+                let stream: TcpStream = match TcpStream::connect(&address).await {
+                    Err(error) => return DockerError::raise_tcp_connect(&address, error),
+                    Ok(value) => value,
+                };
+
+                match tls.connector().connect(name, stream).await {
+                    Err(error) => DockerError::raise_tls_handshake_failed(&address, error),
+                    Ok(stream) => Self::handshake(&address, stream, limits).await,
+                }
+            }
+            DockerEndpoint::Ssh(destination, port) => {
+                let stream: SshStream = SshStream::open(destination, *port)?;
+                Self::handshake(destination, stream, limits).await
+            }
+        }
+    }
 
-        let docker: DockerConnection<T> = match handshake(stream).await {
-            Err(error) => return DockerError::raise_handshake_failed(socket, error),
+    async fn handshake<S>(name: &str, stream: S, limits: DockerResponseLimits) -> DockerResult<Self>
+    where
+        S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    {
+        let docker: DockerConnection<T> = match handshake(TokioIo::new(stream)).await {
+            Err(error) => return DockerError::raise_handshake_failed(name, error),
             Ok((sender, connection)) => Self {
                 sender: sender,
-                connection: spawn(async move { connection.await }),
+                connection: spawn(async move { connection.with_upgrades().await }),
+                limits: limits,
+                pool: None,
             },
         };
 
@@ -106,7 +263,7 @@ where
         };
 
         let status: StatusCode = response.status();
-        let response: DockerResponse = DockerResponse::new(url, response, self.connection);
+        let response: DockerResponse = DockerResponse::new(url, response, self.connection, self.limits, None);
 
         if !status.is_success() {
             return DockerError::raise_status_failed(status, response);
@@ -116,12 +273,31 @@ where
     }
 
     pub async fn put(self, url: &str, data: T) -> DockerResult<DockerResponse> {
-        let request = Request::builder()
+        self.send_archive("PUT", url, data, &[]).await
+    }
+
+    pub async fn post_archive(self, url: &str, data: T, headers: &[(&str, &str)]) -> DockerResult<DockerResponse> {
+        self.send_archive("POST", url, data, headers).await
+    }
+
+    async fn send_archive(
+        self,
+        method: &str,
+        url: &str,
+        data: T,
+        headers: &[(&str, &str)],
+    ) -> DockerResult<DockerResponse> {
+        let mut request = Request::builder()
             .uri(url)
-            .method("PUT")
+            .method(method)
             .header("Host", "localhost")
-            .header("Content-Type", "application/x-tar")
-            .body(data);
+            .header("Content-Type", "application/x-tar");
+
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        let request = request.body(data);
 
         let request: Request<T> = match request {
             Err(error) => return DockerError::raise_builder_failed(url, error),
@@ -133,6 +309,84 @@ where
 }
 
 impl DockerConnection<Full<Bytes>> {
+    pub async fn reuse(
+        pool: &DockerPool,
+        endpoint: &DockerEndpoint,
+        limits: DockerResponseLimits,
+    ) -> DockerResult<Self> {
+        while let Some((mut sender, connection)) = pool.take() {
+            if sender.ready().await.is_ok() {
+                return Ok(Self {
+                    sender: sender,
+                    connection: connection,
+                    limits: limits,
+                    pool: Some(pool.clone()),
+                });
+            }
+        }
+
+        let docker: Self = Self::open(endpoint, limits).await?;
+
+        Ok(Self {
+            pool: Some(pool.clone()),
+            ..docker
+        })
+    }
+
+    async fn execute_reused(mut self, url: &str, request: Request<Full<Bytes>>) -> DockerResult<DockerResponse> {
+        let response: Response<Incoming> = match self.sender.send_request(request).await {
+            Err(error) => return DockerError::raise_request_failed(url, error),
+            Ok(value) => value,
+        };
+
+        let reuse: Option<DockerReuse> = self.pool.map(|pool| DockerReuse {
+            sender: self.sender,
+            pool: pool,
+        });
+
+        let status: StatusCode = response.status();
+        let response: DockerResponse = DockerResponse::new(url, response, self.connection, self.limits, reuse);
+
+        if !status.is_success() {
+            return DockerError::raise_status_failed(status, response);
+        }
+
+        Ok(response)
+    }
+
+    pub async fn upgrade(mut self, url: &str) -> DockerResult<TokioIo<Upgraded>> {
+        let request = Request::builder()
+            .uri(url)
+            .method("POST")
+            .header("Host", "localhost")
+            .header("Connection", "Upgrade")
+            .header("Upgrade", "tcp")
+            .body(Full::new(Bytes::new()));
+
+        let request: Request<Full<Bytes>> = match request {
+            Err(error) => return DockerError::raise_builder_failed(url, error),
+            Ok(value) => value,
+        };
+
+        let response: Response<Incoming> = match self.sender.send_request(request).await {
+            Err(error) => return DockerError::raise_request_failed(url, error),
+            Ok(value) => value,
+        };
+
+        let status: StatusCode = response.status();
+        if status != StatusCode::SWITCHING_PROTOCOLS {
+            let response: DockerResponse = DockerResponse::new(url, response, self.connection, self.limits, None);
+            return DockerError::raise_status_failed(status, response);
+        }
+
+        // after switching protocols the connection task completes
+        // and the raw socket is handed over to the upgraded io
+        match upgrade::on(response).await {
+            Err(error) => DockerError::raise_upgrade_failed(url, error),
+            Ok(upgraded) => Ok(TokioIo::new(upgraded)),
+        }
+    }
+
     pub async fn get(self, url: &str) -> DockerResult<DockerResponse> {
         let request = Request::builder()
             .uri(url)
@@ -145,16 +399,29 @@ impl DockerConnection<Full<Bytes>> {
             Ok(value) => value,
         };
 
-        self.execute(url, request).await
+        self.execute_reused(url, request).await
     }
 
     pub async fn post(self, url: &str, body: Option<Value>) -> DockerResult<DockerResponse> {
-        let request = Request::builder()
+        self.post_with_headers(url, body, &[]).await
+    }
+
+    pub async fn post_with_headers(
+        self,
+        url: &str,
+        body: Option<Value>,
+        headers: &[(&str, &str)],
+    ) -> DockerResult<DockerResponse> {
+        let mut request = Request::builder()
             .uri(url)
             .method("POST")
             .header("Host", "localhost")
             .header("Content-Type", "application/json");
 
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
         let request = match body {
             None => request.body(Full::new(Bytes::new())),
             Some(value) => request.body(Full::new(Bytes::from(value.to_string()))),
@@ -165,7 +432,7 @@ impl DockerConnection<Full<Bytes>> {
             Ok(value) => value,
         };
 
-        self.execute(url, request).await
+        self.execute_reused(url, request).await
     }
 
     pub async fn delete(self, url: &str) -> DockerResult<DockerResponse> {
@@ -180,6 +447,6 @@ impl DockerConnection<Full<Bytes>> {
             Ok(value) => value,
         };
 
-        self.execute(url, request).await
+        self.execute_reused(url, request).await
     }
 }
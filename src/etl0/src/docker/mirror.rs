@@ -0,0 +1,103 @@
+use super::error::{DockerError, DockerResult};
+use super::types::ImageReference;
+
+const DOCKER_HUB: &str = "docker.io";
+
+// rewrites images of one registry to another one, the rule
+// 'docker.io/* -> mirror.internal/docker/*' pulls 'ubuntu' as
+// 'mirror.internal/docker/library/ubuntu'
+#[derive(Debug, Clone)]
+pub struct RegistryMirror {
+    from: String,
+    to: String,
+}
+
+impl RegistryMirror {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.trim_end_matches('*').trim_end_matches('/').to_owned(),
+            to: to.trim_end_matches('*').trim_end_matches('/').to_owned(),
+        }
+    }
+
+    // accepts rules in the form 'source/* -> target/*'
+    pub fn parse(rule: &str) -> DockerResult<Self> {
+        let (from, to) = match rule.split_once("->") {
+            Some((from, to)) => (from.trim(), to.trim()),
+            None => return DockerError::raise_mirror_invalid(rule, "expected 'source/* -> target/*'"),
+        };
+
+        match (from.strip_suffix("/*"), to.strip_suffix("/*")) {
+            (Some(from), Some(to)) if !from.is_empty() && !to.is_empty() => Ok(Self::new(from, to)),
+            _ => DockerError::raise_mirror_invalid(rule, "both sides have to end with '/*'"),
+        }
+    }
+
+    // honors ETL0_REGISTRY_MIRROR and leaves images untouched when unset
+    pub fn from_env() -> DockerResult<Option<Self>> {
+        match std::env::var("ETL0_REGISTRY_MIRROR") {
+            Ok(value) if !value.is_empty() => Ok(Some(Self::parse(&value)?)),
+            _ => Ok(None),
+        }
+    }
+
+    // short docker hub names are expanded first, so 'ubuntu'
+    // and 'docker.io/library/ubuntu' are matched by the same rule
+    pub fn rewrite(&self, name: &str) -> Option<String> {
+        let qualified: String = match name.split_once('/') {
+            None => format!("{DOCKER_HUB}/library/{name}"),
+            Some((host, _)) if host.contains(['.', ':']) || host == "localhost" => name.to_owned(),
+            Some(_) => format!("{DOCKER_HUB}/{name}"),
+        };
+
+        match qualified.strip_prefix(&self.from) {
+            Some(rest) if rest.starts_with('/') => Some(format!("{}{rest}", self.to)),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> ImageReference<'a> {
+    // the name to pull instead, tag, digest and platform stay the same
+    pub fn mirror(&self, mirror: &RegistryMirror) -> Option<String> {
+        mirror.rewrite(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_docker_hub_images() {
+        let mirror: RegistryMirror = RegistryMirror::parse("docker.io/* -> mirror.internal/docker/*").unwrap();
+
+        let rewrite = |value: &str| ImageReference::parse(value).mirror(&mirror);
+
+        assert_eq!(
+            rewrite("ubuntu:22.04").as_deref(),
+            Some("mirror.internal/docker/library/ubuntu")
+        );
+        assert_eq!(
+            rewrite("amacal/etl0").as_deref(),
+            Some("mirror.internal/docker/amacal/etl0")
+        );
+        assert_eq!(
+            rewrite("docker.io/library/python").as_deref(),
+            Some("mirror.internal/docker/library/python")
+        );
+        assert_eq!(rewrite("ghcr.io/amacal/etl0:1.0"), None);
+        assert_eq!(rewrite("localhost:5000/etl0"), None);
+    }
+
+    #[test]
+    fn rejects_rules_without_wildcards() {
+        assert_eq!(
+            RegistryMirror::parse("docker.io -> mirror.internal")
+                .unwrap_err()
+                .code(),
+            "E035"
+        );
+        assert_eq!(RegistryMirror::parse("docker.io/*").unwrap_err().code(), "E035");
+    }
+}
@@ -1,9 +1,38 @@
+mod auth;
+mod body;
 mod client;
+mod endpoint;
 mod error;
 mod http;
+mod mirror;
+mod networks;
+mod owner;
+mod pages;
+mod preflight;
+mod query;
+mod run;
+mod ssh;
+mod stdin;
 mod stream;
+mod system;
 mod tar;
+mod tls;
 mod types;
+mod volumes;
 
+pub use self::auth::RegistryAuth;
 pub use self::client::DockerClient;
+pub use self::endpoint::DockerEndpoint;
+pub use self::error::{DockerError, DockerResult};
+pub use self::http::DockerResponse;
+pub use self::mirror::RegistryMirror;
+pub use self::networks::*;
+pub use self::owner::{DockerOwner, OrphanCleanup, LABEL_PIPELINE, LABEL_RUN, LABEL_TASK};
+pub use self::pages::ContainerListPages;
+pub use self::preflight::SystemPreflight;
+pub use self::run::{ContainerRun, ContainerRunStep, RunHandle};
+pub use self::stdin::ContainerStdin;
+pub use self::system::*;
+pub use self::tls::DockerTls;
 pub use self::types::*;
+pub use self::volumes::*;
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::client::DockerClient;
+use super::error::{DockerError, DockerResult};
+use super::http::DockerConnection;
+use super::query::encode_json;
+use super::types::ErrorResponse;
+
+#[derive(Debug, Clone, Default)]
+pub struct NetworkCreateSpec<'a> {
+    pub name: &'a str,
+    pub driver: Option<&'a str>,
+    pub internal: bool,
+    pub labels: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> NetworkCreateSpec<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            ..Self::default()
+        }
+    }
+
+    pub fn driver(self, value: &'a str) -> Self {
+        Self {
+            driver: Some(value),
+            ..self
+        }
+    }
+
+    pub fn internal(self, value: bool) -> Self {
+        Self {
+            internal: value,
+            ..self
+        }
+    }
+
+    pub fn label(mut self, key: &'a str, value: &'a str) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "Name": self.name,
+            "Driver": self.driver.unwrap_or("bridge"),
+            "Internal": self.internal,
+            "Labels": self.labels,
+            "CheckDuplicate": true,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct NetworkPruneFilters<'a> {
+    labels: Vec<&'a str>,
+    excluded_labels: Vec<&'a str>,
+}
+
+impl<'a> NetworkPruneFilters<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn label(mut self, value: &'a str) -> Self {
+        self.labels.push(value);
+        self
+    }
+
+    pub fn excluded_label(mut self, value: &'a str) -> Self {
+        self.excluded_labels.push(value);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        json!({"label": self.labels, "label!": self.excluded_labels})
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkCreateResponse {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(rename = "Warning")]
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NetworkPruneResponse {
+    #[serde(rename = "NetworksDeleted")]
+    pub networks_deleted: Option<Vec<String>>,
+}
+
+#[derive(Debug)]
+pub enum NetworkCreate {
+    Succeeded(NetworkCreateResponse),
+    BadParameter(ErrorResponse),
+    Forbidden(ErrorResponse),
+    NoSuchPlugin(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum NetworkConnect {
+    Succeeded,
+    BadParameter(ErrorResponse),
+    Forbidden(ErrorResponse),
+    NoSuchNetworkOrContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum NetworkDisconnect {
+    Succeeded,
+    Forbidden(ErrorResponse),
+    NoSuchNetworkOrContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum NetworkRemove {
+    Succeeded,
+    Forbidden(ErrorResponse),
+    NoSuchNetwork(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum NetworkPrune {
+    Succeeded(NetworkPruneResponse),
+    ServerError(ErrorResponse),
+}
+
+impl DockerClient {
+    pub async fn networks_create(&self, spec: &NetworkCreateSpec<'_>) -> DockerResult<NetworkCreate> {
+        let url: String = format!("{}/networks/create", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(self.stamp(spec.to_json()))).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(NetworkCreate::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(NetworkCreate::BadParameter(response.into_error().await?)),
+                    403 => Ok(NetworkCreate::Forbidden(response.into_error().await?)),
+                    404 => Ok(NetworkCreate::NoSuchPlugin(response.into_error().await?)),
+                    500 => Ok(NetworkCreate::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn networks_connect(&self, id: &str, container: &str, aliases: &[&str]) -> DockerResult<NetworkConnect> {
+        let url: String = format!("{}/networks/{id}/connect", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+        let payload: Value = json!({"Container": container, "EndpointConfig": {"Aliases": aliases}});
+
+        match connection.post(&url, Some(payload)).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(NetworkConnect::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    400 => Ok(NetworkConnect::BadParameter(response.into_error().await?)),
+                    403 => Ok(NetworkConnect::Forbidden(response.into_error().await?)),
+                    404 => Ok(NetworkConnect::NoSuchNetworkOrContainer(response.into_error().await?)),
+                    500 => Ok(NetworkConnect::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn networks_disconnect(&self, id: &str, container: &str, force: bool) -> DockerResult<NetworkDisconnect> {
+        let url: String = format!("{}/networks/{id}/disconnect", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+        let payload: Value = json!({"Container": container, "Force": force});
+
+        match connection.post(&url, Some(payload)).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(NetworkDisconnect::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    403 => Ok(NetworkDisconnect::Forbidden(response.into_error().await?)),
+                    404 => Ok(NetworkDisconnect::NoSuchNetworkOrContainer(
+                        response.into_error().await?,
+                    )),
+                    500 => Ok(NetworkDisconnect::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn networks_remove(&self, id: &str) -> DockerResult<NetworkRemove> {
+        let url: String = format!("{}/networks/{id}", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.delete(&url).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(NetworkRemove::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    403 => Ok(NetworkRemove::Forbidden(response.into_error().await?)),
+                    404 => Ok(NetworkRemove::NoSuchNetwork(response.into_error().await?)),
+                    500 => Ok(NetworkRemove::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn networks_prune(&self, filters: &NetworkPruneFilters<'_>) -> DockerResult<NetworkPrune> {
+        let url: String = format!(
+            "{}/networks/prune?filters={}",
+            self.prefix,
+            encode_json(&filters.to_json())
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(NetworkPrune::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(NetworkPrune::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+}
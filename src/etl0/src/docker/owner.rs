@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use super::client::DockerClient;
+use super::error::{DockerError, DockerResult};
+use super::networks::{NetworkPrune, NetworkPruneFilters};
+use super::types::*;
+use super::volumes::{VolumeFilters, VolumeInfo, VolumeList, VolumeRemove};
+
+pub const LABEL_PIPELINE: &str = "etl0.pipeline";
+pub const LABEL_TASK: &str = "etl0.task";
+pub const LABEL_RUN: &str = "etl0.run";
+
+#[derive(Debug, Clone)]
+pub struct DockerOwner {
+    pub pipeline: String,
+    pub task: String,
+    pub run: String,
+}
+
+impl DockerOwner {
+    pub fn new(pipeline: &str, task: &str, run: &str) -> Self {
+        Self {
+            pipeline: pipeline.to_owned(),
+            task: task.to_owned(),
+            run: run.to_owned(),
+        }
+    }
+
+    pub fn labels(&self) -> [(&'static str, &str); 3] {
+        [
+            (LABEL_PIPELINE, &self.pipeline),
+            (LABEL_TASK, &self.task),
+            (LABEL_RUN, &self.run),
+        ]
+    }
+
+    fn owns(&self, labels: Option<&HashMap<String, String>>) -> bool {
+        labels.and_then(|labels| labels.get(LABEL_RUN)) == Some(&self.run)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct OrphanCleanup {
+    pub containers: Vec<String>,
+    pub volumes: Vec<String>,
+    pub networks: Vec<String>,
+}
+
+impl DockerClient {
+    // removes created, exited and dead containers carrying the run label,
+    // except those of the run the client itself belongs to, running ones may
+    // still serve a live run; volumes and networks go after containers,
+    // because they cannot be removed while still in use
+    pub async fn cleanup_orphans(&self) -> DockerResult<OrphanCleanup> {
+        let mut cleanup: OrphanCleanup = OrphanCleanup::default();
+        let current: Option<&DockerOwner> = self.owner();
+
+        let options: ContainerListOptions = ContainerListOptions::new()
+            .all(true)
+            .label(LABEL_RUN)
+            .status("created")
+            .status("exited")
+            .status("dead");
+
+        let containers: Vec<ContainerInfo> = match self.containers_list(&options).await? {
+            ContainerList::Succeeded(value) => value,
+            ContainerList::BadParameter(value) => {
+                return DockerError::raise_cleanup_failed("containers", value.message)
+            }
+            ContainerList::ServerError(value) => return DockerError::raise_cleanup_failed("containers", value.message),
+        };
+
+        for container in containers {
+            if current.is_some_and(|owner| owner.owns(container.labels.as_ref())) {
+                continue;
+            }
+
+            match self.containers_remove(&container.id, true).await? {
+                ContainerRemove::Succeeded => cleanup.containers.push(container.id),
+                ContainerRemove::NoSuchContainer(_) => (),
+                ContainerRemove::BadParameter(value) => {
+                    return DockerError::raise_cleanup_failed("containers", value.message)
+                }
+                ContainerRemove::Conflict(value) => {
+                    return DockerError::raise_cleanup_failed("containers", value.message)
+                }
+                ContainerRemove::ServerError(value) => {
+                    return DockerError::raise_cleanup_failed("containers", value.message)
+                }
+            }
+        }
+
+        let filters: VolumeFilters = VolumeFilters::new().label(LABEL_RUN);
+
+        let volumes: Vec<VolumeInfo> = match self.volumes_list(&filters).await? {
+            VolumeList::Succeeded(value) => value.volumes.unwrap_or_default(),
+            VolumeList::ServerError(value) => return DockerError::raise_cleanup_failed("volumes", value.message),
+        };
+
+        for volume in volumes {
+            if current.is_some_and(|owner| owner.owns(volume.labels.as_ref())) {
+                continue;
+            }
+
+            // volumes still mounted by a live run are left alone
+            match self.volumes_remove(&volume.name, true).await? {
+                VolumeRemove::Succeeded => cleanup.volumes.push(volume.name),
+                VolumeRemove::NoSuchVolume(_) | VolumeRemove::Conflict(_) => (),
+                VolumeRemove::ServerError(value) => return DockerError::raise_cleanup_failed("volumes", value.message),
+            }
+        }
+
+        let excluded: Option<String> = current.map(|owner| format!("{LABEL_RUN}={}", owner.run));
+        let mut filters: NetworkPruneFilters = NetworkPruneFilters::new().label(LABEL_RUN);
+
+        if let Some(value) = &excluded {
+            filters = filters.excluded_label(value);
+        }
+
+        match self.networks_prune(&filters).await? {
+            NetworkPrune::Succeeded(value) => cleanup.networks = value.networks_deleted.unwrap_or_default(),
+            NetworkPrune::ServerError(value) => return DockerError::raise_cleanup_failed("networks", value.message),
+        }
+
+        Ok(cleanup)
+    }
+}
@@ -0,0 +1,47 @@
+use super::client::DockerClient;
+use super::error::DockerResult;
+use super::types::*;
+
+// walks the container list newest first, one bounded request per page,
+// each next page asks only for containers created before the last one seen
+#[derive(Debug)]
+pub struct ContainerListPages<'a> {
+    client: &'a DockerClient,
+    options: ContainerListOptions,
+    size: u32,
+    exhausted: bool,
+}
+
+impl<'a> ContainerListPages<'a> {
+    pub async fn next(&mut self) -> DockerResult<Option<ContainerList>> {
+        if self.exhausted {
+            return Ok(None);
+        }
+
+        let options: ContainerListOptions = self.options.clone().limit(self.size);
+        let page: ContainerList = self.client.containers_list(&options).await?;
+
+        match &page {
+            ContainerList::Succeeded(containers) => match containers.last() {
+                Some(last) if containers.len() as u32 >= self.size => {
+                    self.options = self.options.clone().before(&last.id);
+                }
+                _ => self.exhausted = true,
+            },
+            ContainerList::BadParameter(_) | ContainerList::ServerError(_) => self.exhausted = true,
+        }
+
+        Ok(Some(page))
+    }
+}
+
+impl DockerClient {
+    pub fn containers_pages(&self, options: &ContainerListOptions, size: u32) -> ContainerListPages<'_> {
+        ContainerListPages {
+            client: self,
+            options: options.clone(),
+            size: size.max(1),
+            exhausted: false,
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use std::path::Path;
+
+use super::client::DockerClient;
+use super::endpoint::DockerEndpoint;
+use super::error::DockerResult;
+use super::system::{SystemInfo, SystemInfoResponse};
+use super::types::*;
+
+#[derive(Debug)]
+pub enum SystemPreflight {
+    Succeeded,
+    Failed(Vec<String>),
+}
+
+impl DockerClient {
+    // answers whether the spec could be run without creating anything,
+    // so a run can fail with all the reasons before it mutates the host
+    pub async fn preflight(&self, spec: &ContainerCreateSpec<'_>) -> DockerResult<SystemPreflight> {
+        let mut problems: Vec<String> = Vec::new();
+        self.check().await?;
+
+        match self.containers_list(&ContainerListOptions::new().limit(1)).await? {
+            ContainerList::Succeeded(_) => (),
+            ContainerList::BadParameter(value) | ContainerList::ServerError(value) => problems.push(format!(
+                "Cannot list containers, the daemon answered '{}'",
+                value.message
+            )),
+        }
+
+        let info: SystemInfoResponse = match self.system_info().await? {
+            SystemInfo::Succeeded(value) => *value,
+            SystemInfo::ServerError(value) => {
+                problems.push(format!(
+                    "Cannot read daemon info, the daemon answered '{}'",
+                    value.message
+                ));
+                return Ok(SystemPreflight::Failed(problems));
+            }
+        };
+
+        let options: &[String] = info.security_options.as_deref().unwrap_or_default();
+        let rootless: bool = options.iter().any(|value| value.contains("name=rootless"));
+        let userns: bool = options.iter().any(|value| value.contains("name=userns"));
+
+        if spec.host_config.privileged && rootless {
+            problems.push("Privileged mode is requested, but the daemon runs rootless and cannot grant it".to_owned());
+        }
+
+        if spec.host_config.privileged && userns {
+            problems.push("Privileged mode is requested, but the daemon remaps users with userns".to_owned());
+        }
+
+        if !spec.host_config.devices.is_empty() && rootless {
+            problems.push("Devices are requested, but the daemon runs rootless and may not access them".to_owned());
+        }
+
+        // bind sources are resolved on the daemon host, which is only the
+        // local machine behind a unix socket; a source which is not an
+        // absolute path names a volume and has nothing to check on disk
+        if let DockerEndpoint::Unix(_) = self.endpoint {
+            let binds = spec
+                .host_config
+                .binds
+                .iter()
+                .filter_map(|value| value.split(':').next());
+            let mounts = spec.host_config.mounts.iter().filter_map(|value| match value {
+                ContainerMount::Bind(source, _, _) => Some(*source),
+                _ => None,
+            });
+
+            for source in binds.chain(mounts).filter(|source| Path::new(source).is_absolute()) {
+                if let Err(error) = tokio::fs::metadata(source).await {
+                    problems.push(format!("Cannot bind '{source}' into the container, because '{error}'"));
+                }
+            }
+        }
+
+        match problems.is_empty() {
+            true => Ok(SystemPreflight::Succeeded),
+            false => Ok(SystemPreflight::Failed(problems)),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde_json::Value;
+
+pub(crate) fn encode(value: &str) -> String {
+    let mut result: String = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => result.push(byte as char),
+            byte => result.push_str(&format!("%{byte:02X}")),
+        }
+    }
+
+    result
+}
+
+pub(crate) fn encode_json(value: &Value) -> String {
+    encode(&value.to_string())
+}
@@ -0,0 +1,166 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::runtime::Handle;
+use tokio_stream::Stream;
+
+use super::client::DockerClient;
+use super::error::DockerResult;
+use super::types::*;
+use crate::tar::TarArchive;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerRunStep {
+    Create,
+    Upload,
+    Attach,
+    Start,
+}
+
+#[derive(Debug)]
+pub enum ContainerRun {
+    Succeeded(RunHandle),
+    Rejected(ContainerRunStep, ErrorResponse),
+}
+
+#[derive(Debug)]
+pub struct RunHandle {
+    id: String,
+    client: Box<DockerClient>,
+    logs: ContainerLogsStream,
+    removed: bool,
+}
+
+impl RunHandle {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    // waits for the container to stop and removes it afterwards,
+    // logs not consumed until then are dropped with the handle
+    pub async fn wait(mut self) -> DockerResult<ContainerWait> {
+        let outcome: ContainerWait = match self
+            .client
+            .containers_wait(&self.id, ContainerWaitCondition::NotRunning)
+            .await
+        {
+            Ok(ContainerWait::Succeeded(value)) => ContainerWait::Succeeded(value),
+            outcome => {
+                // the container may still be running when waiting failed
+                self.client.discard(&self.id).await;
+                self.removed = true;
+                return outcome;
+            }
+        };
+
+        // a failed removal leaves the cleanup to the drop
+        if let ContainerRemove::Succeeded = self.client.containers_remove(&self.id, false).await? {
+            self.removed = true;
+        }
+
+        Ok(outcome)
+    }
+}
+
+impl Stream for RunHandle {
+    type Item = DockerResult<ContainerLogLine>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let pointer = &mut self_mut.logs;
+        let pin = Pin::new(pointer);
+
+        pin.poll_next(cx)
+    }
+}
+
+impl Drop for RunHandle {
+    fn drop(&mut self) {
+        if self.removed {
+            return;
+        }
+
+        // dropping without waiting means the run was abandoned or cancelled,
+        // the container is still killed and removed in the background
+        if let Ok(handle) = Handle::try_current() {
+            let client: DockerClient = DockerClient::clone(&self.client);
+            let id: String = self.id.clone();
+
+            handle.spawn(async move { client.discard(&id).await });
+        }
+    }
+}
+
+impl DockerClient {
+    pub async fn run(
+        &self,
+        spec: &ContainerCreateSpec<'_>,
+        uploads: Vec<(&str, TarArchive)>,
+    ) -> DockerResult<ContainerRun> {
+        let id: String = match self.containers_create(spec).await? {
+            ContainerCreate::Succeeded(value) => value.id,
+            ContainerCreate::BadParameter(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Create, value)),
+            ContainerCreate::NoSuchImage(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Create, value)),
+            ContainerCreate::Conflict(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Create, value)),
+            ContainerCreate::ServerError(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Create, value)),
+        };
+
+        match self.run_created(&id, uploads).await {
+            Ok(ContainerRun::Succeeded(handle)) => Ok(ContainerRun::Succeeded(handle)),
+            outcome => {
+                self.discard(&id).await;
+                outcome
+            }
+        }
+    }
+
+    async fn run_created(&self, id: &str, uploads: Vec<(&str, TarArchive)>) -> DockerResult<ContainerRun> {
+        for (path, archive) in uploads {
+            match self.container_upload(id, path, archive).await? {
+                ContainerUpload::Succeeded => (),
+                ContainerUpload::BadParameter(value) => {
+                    return Ok(ContainerRun::Rejected(ContainerRunStep::Upload, value))
+                }
+                ContainerUpload::PermissionDenied(value) => {
+                    return Ok(ContainerRun::Rejected(ContainerRunStep::Upload, value))
+                }
+                ContainerUpload::NoSuchContainer(value) => {
+                    return Ok(ContainerRun::Rejected(ContainerRunStep::Upload, value))
+                }
+                ContainerUpload::ServerError(value) => {
+                    return Ok(ContainerRun::Rejected(ContainerRunStep::Upload, value))
+                }
+            }
+        }
+
+        // attaching before starting guarantees no early output is lost
+        let logs: ContainerLogsStream = match self.containers_attach(id).await? {
+            ContainerAttach::Succeeded(value) => value,
+            ContainerAttach::BadParameter(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Attach, value)),
+            ContainerAttach::NoSuchContainer(value) => {
+                return Ok(ContainerRun::Rejected(ContainerRunStep::Attach, value))
+            }
+            ContainerAttach::ServerError(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Attach, value)),
+        };
+
+        match self.containers_start(id).await? {
+            ContainerStart::Succeeded | ContainerStart::AlreadyStarted => (),
+            ContainerStart::NoSuchContainer(value) => {
+                return Ok(ContainerRun::Rejected(ContainerRunStep::Start, value))
+            }
+            ContainerStart::ServerError(value) => return Ok(ContainerRun::Rejected(ContainerRunStep::Start, value)),
+        }
+
+        Ok(ContainerRun::Succeeded(RunHandle {
+            id: id.to_owned(),
+            client: Box::new(self.clone()),
+            logs,
+            removed: false,
+        }))
+    }
+
+    async fn discard(&self, id: &str) {
+        let _ = self.containers_kill(id, None).await;
+        let _ = self.containers_remove(id, true).await;
+    }
+}
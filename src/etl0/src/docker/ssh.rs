@@ -0,0 +1,70 @@
+use std::pin::Pin;
+use std::process::Stdio;
+use std::task::{Context, Poll};
+
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+
+use super::error::{DockerError, DockerResult};
+
+// tunnels the API through 'docker system dial-stdio' executed on the
+// remote host, the same way the docker cli talks to ssh:// endpoints
+#[derive(Debug)]
+pub struct SshStream {
+    _child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+}
+
+impl SshStream {
+    pub fn open(destination: &str, port: Option<u16>) -> DockerResult<Self> {
+        let mut command: Command = Command::new("ssh");
+
+        if let Some(port) = port {
+            command.arg("-p").arg(port.to_string());
+        }
+
+        command
+            .arg("--")
+            .arg(destination)
+            .args(["docker", "system", "dial-stdio"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .kill_on_drop(true);
+
+        let mut child: Child = match command.spawn() {
+            Err(error) => return DockerError::raise_ssh_spawn_failed(destination, error),
+            Ok(value) => value,
+        };
+
+        match (child.stdin.take(), child.stdout.take()) {
+            (Some(stdin), Some(stdout)) => Ok(Self {
+                _child: child,
+                stdin,
+                stdout,
+            }),
+            _ => DockerError::raise_ssh_spawn_failed(destination, std::io::ErrorKind::BrokenPipe.into()),
+        }
+    }
+}
+
+impl AsyncRead for SshStream {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdout).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for SshStream {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.get_mut().stdin).poll_write(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdin).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().stdin).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,53 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
+use tokio::io::{AsyncWrite, AsyncWriteExt, WriteHalf};
+
+use super::error::{DockerError, DockerResult};
+
+#[derive(Debug)]
+pub struct ContainerStdin {
+    url: String,
+    inner: WriteHalf<TokioIo<Upgraded>>,
+}
+
+impl ContainerStdin {
+    pub(crate) fn from(url: &str, inner: WriteHalf<TokioIo<Upgraded>>) -> Self {
+        Self {
+            url: url.to_owned(),
+            inner,
+        }
+    }
+
+    pub async fn write(&mut self, data: &[u8]) -> DockerResult<()> {
+        match self.inner.write_all(data).await {
+            Err(error) => DockerError::raise_upgraded_io_failed(&self.url, error),
+            Ok(()) => Ok(()),
+        }
+    }
+
+    // half-closes the hijacked socket, so the process reading
+    // stdin sees EOF while its output can be still received
+    pub async fn close(mut self) -> DockerResult<()> {
+        match self.inner.shutdown().await {
+            Err(error) => DockerError::raise_upgraded_io_failed(&self.url, error),
+            Ok(()) => Ok(()),
+        }
+    }
+}
+
+impl AsyncWrite for ContainerStdin {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, data: &[u8]) -> Poll<Result<usize, std::io::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_write(cx, data)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), std::io::Error>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -2,10 +2,17 @@ use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use hyper::body::{Body, Bytes, Frame, Incoming};
+use hyper::upgrade::Upgraded;
 use hyper::Response;
+use hyper_util::rt::TokioIo;
+use serde::de::DeserializeOwned;
+use serde_json::from_slice;
+use tokio::io::{AsyncRead, ReadBuf, ReadHalf};
 use tokio::task::JoinHandle;
+use tokio::time::{sleep, Sleep};
 use tokio_stream::Stream;
 
 use crate::docker::error::{DockerError, DockerResult};
@@ -14,10 +21,19 @@ use crate::docker::http::DockerResponse;
 #[derive(Debug)]
 pub struct DockerStreamBuffer {
     position: usize,
+    consumed: usize,
     data: Vec<u8>,
 }
 
 impl DockerStreamBuffer {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            position: 0,
+            consumed: 0,
+            data: vec![0; capacity],
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.position
     }
@@ -37,8 +53,28 @@ impl DockerStreamBuffer {
     }
 
     pub fn consume(&mut self, count: usize) {
+        if cfg!(debug_assertions) && count > self.position {
+            self.violation(&format!(
+                "consumed {count} bytes, but only {} are available",
+                self.position
+            ));
+        }
+
         self.data.copy_within(count..self.position, 0);
         self.position -= count;
+        self.consumed += count;
+    }
+
+    fn describe(&self) -> String {
+        let head: &[u8] = &self.data[0..std::cmp::min(self.position, 32)];
+        format!(
+            "position={}, consumed={}, head={:02x?}",
+            self.position, self.consumed, head
+        )
+    }
+
+    pub fn violation(&self, message: &str) -> ! {
+        panic!("docker stream buffer violation: {message}; {}", self.describe());
     }
 }
 
@@ -51,7 +87,71 @@ impl AsRef<[u8]> for DockerStreamBuffer {
 pub trait DockerStreamHandler {
     type Item;
 
-    fn extract(&self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>>;
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>>;
+
+    // called only in debug builds after each extraction
+    // to verify what the handler left in the buffer
+    fn validate(&self, _buffer: &DockerStreamBuffer) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+// splits the buffer into complete CRLF terminated json documents,
+// leaving any trailing partial line for the next extraction
+pub fn extract_json_lines<T>(buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<T>>
+where
+    T: DeserializeOwned,
+{
+    let mut current: usize = 0;
+    let mut result: Vec<DockerResult<T>> = Vec::new();
+
+    let data = buffer.as_ref();
+
+    for (i, window) in data.windows(2).enumerate() {
+        if window != b"\r\n" {
+            continue;
+        }
+
+        let item: Bytes = Bytes::from(data[current..i].to_vec());
+        let item: DockerResult<T> = match from_slice(&item) {
+            Ok(value) => Ok(value),
+            Err(error) => DockerError::raise_deserialization_failed(None, error, item),
+        };
+
+        result.push(item);
+        current = i + 2;
+    }
+
+    if current > 0 {
+        buffer.consume(current);
+    }
+
+    result
+}
+
+fn extract<H>(handler: &mut H, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<H::Item>>
+where
+    H: DockerStreamHandler,
+{
+    let available: usize = buffer.len();
+    let consumed: usize = buffer.consumed;
+    let items: Vec<DockerResult<H::Item>> = handler.extract(buffer);
+
+    if cfg!(debug_assertions) {
+        if buffer.consumed < consumed || buffer.consumed - consumed != available - buffer.len() {
+            buffer.violation("consumed bytes do not match the shrunk buffer");
+        }
+
+        if !items.is_empty() && buffer.consumed == consumed {
+            buffer.violation(&format!("extracted {} items without consuming any bytes", items.len()));
+        }
+
+        if let Err(message) = handler.validate(buffer) {
+            buffer.violation(&message);
+        }
+    }
+
+    items
 }
 
 #[derive(Debug)]
@@ -62,7 +162,7 @@ where
 {
     handler: H,
     url: String,
-    response: Response<Incoming>,
+    response: Box<Response<Incoming>>,
     connection: JoinHandle<Result<(), hyper::Error>>,
     buffer: Option<DockerStreamBuffer>,
     prefetched: VecDeque<DockerResult<H::Item>>,
@@ -77,13 +177,10 @@ where
         Self {
             handler: handler,
             url: response.url,
-            response: response.inner,
+            response: Box::new(response.inner),
             connection: response.connection,
             prefetched: VecDeque::new(),
-            buffer: Some(DockerStreamBuffer {
-                position: 0,
-                data: vec![0; 65536],
-            }),
+            buffer: Some(DockerStreamBuffer::new(65536)),
         }
     }
 
@@ -103,8 +200,8 @@ where
             Some(buffer) => {
                 let mut broken = false;
 
-                for item in self.handler.extract(buffer) {
-                    if let Err(_) = item {
+                for item in extract(&mut self.handler, buffer) {
+                    if item.is_err() {
                         broken = true;
                     }
 
@@ -143,10 +240,7 @@ where
             },
         }
 
-        match self.prefetched.pop_front() {
-            None => None,
-            Some(line) => Some(Poll::Ready(Some(line))),
-        }
+        self.prefetched.pop_front().map(|line| Poll::Ready(Some(line)))
     }
 
     fn handle_connection_cleanup(
@@ -199,10 +293,7 @@ where
                     Some(value) => {
                         // either we have something to return
                         // or we need to trigger polling again
-                        match self_mut.handle_hyper_frame(value, &url) {
-                            None => None,
-                            Some(value) => Some(value),
-                        }
+                        self_mut.handle_hyper_frame(value, &url)
                     }
                 },
                 Poll::Pending => Some(Poll::Pending),
@@ -215,3 +306,184 @@ where
         }
     }
 }
+
+#[derive(Debug)]
+pub struct DockerStreamBatch<H>
+where
+    H: DockerStreamHandler + Sized,
+    H::Item: Sized,
+{
+    inner: DockerStream<H>,
+    lines: usize,
+    interval: Option<Duration>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    batch: Vec<H::Item>,
+    failed: Option<DockerError>,
+    completed: bool,
+}
+
+impl<H> DockerStreamBatch<H>
+where
+    H: DockerStreamHandler + Sized,
+    H::Item: Sized,
+{
+    pub fn from(inner: DockerStream<H>, lines: usize, interval: Option<Duration>) -> Self {
+        Self {
+            inner: inner,
+            lines: std::cmp::max(lines, 1),
+            interval: interval,
+            deadline: None,
+            batch: Vec::new(),
+            failed: None,
+            completed: false,
+        }
+    }
+
+    fn push(&mut self, item: H::Item) {
+        if self.batch.is_empty() {
+            self.deadline = self.interval.map(|value| Box::pin(sleep(value)));
+        }
+
+        self.batch.push(item);
+    }
+
+    fn flush(&mut self) -> Poll<Option<DockerResult<Vec<H::Item>>>> {
+        self.deadline = None;
+        Poll::Ready(Some(Ok(std::mem::take(&mut self.batch))))
+    }
+}
+
+impl<H> Stream for DockerStreamBatch<H>
+where
+    H: DockerStreamHandler + Sized + Unpin,
+    H::Item: Sized + Unpin,
+{
+    type Item = DockerResult<Vec<H::Item>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+
+        // an error is reported only after the lines
+        // collected before it were handed over
+        if let Some(error) = self_mut.failed.take() {
+            return Poll::Ready(Some(Err(error)));
+        }
+
+        while !self_mut.completed {
+            let pointer = &mut self_mut.inner;
+            let pin = Pin::new(pointer);
+
+            match pin.poll_next(cx) {
+                Poll::Ready(Some(Ok(item))) => self_mut.push(item),
+                Poll::Ready(Some(Err(error))) => match self_mut.batch.is_empty() {
+                    true => return Poll::Ready(Some(Err(error))),
+                    false => {
+                        self_mut.failed = Some(error);
+                        return self_mut.flush();
+                    }
+                },
+                Poll::Ready(None) => self_mut.completed = true,
+                Poll::Pending => {
+                    let elapsed = match &mut self_mut.deadline {
+                        None => false,
+                        Some(deadline) => deadline.as_mut().poll(cx).is_ready(),
+                    };
+
+                    if elapsed && !self_mut.batch.is_empty() {
+                        return self_mut.flush();
+                    }
+
+                    return Poll::Pending;
+                }
+            }
+
+            if self_mut.batch.len() >= self_mut.lines {
+                return self_mut.flush();
+            }
+        }
+
+        match self_mut.batch.is_empty() {
+            true => Poll::Ready(None),
+            false => self_mut.flush(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DockerIoStream<H>
+where
+    H: DockerStreamHandler + Sized,
+    H::Item: Sized,
+{
+    handler: H,
+    url: String,
+    io: ReadHalf<TokioIo<Upgraded>>,
+    chunk: Vec<u8>,
+    buffer: DockerStreamBuffer,
+    prefetched: VecDeque<DockerResult<H::Item>>,
+    completed: bool,
+}
+
+impl<H> DockerIoStream<H>
+where
+    H: DockerStreamHandler + Sized,
+    H::Item: Sized,
+{
+    pub fn from(handler: H, url: &str, io: ReadHalf<TokioIo<Upgraded>>) -> Self {
+        Self {
+            handler: handler,
+            url: url.to_owned(),
+            io: io,
+            chunk: vec![0; 65536],
+            buffer: DockerStreamBuffer::new(65536),
+            prefetched: VecDeque::new(),
+            completed: false,
+        }
+    }
+}
+
+impl<H> Stream for DockerIoStream<H>
+where
+    H: DockerStreamHandler + Sized + Unpin,
+    H::Item: Sized + Unpin,
+{
+    type Item = DockerResult<H::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+
+        loop {
+            if let Some(item) = self_mut.prefetched.pop_front() {
+                return Poll::Ready(Some(item));
+            }
+
+            if self_mut.completed {
+                return Poll::Ready(None);
+            }
+
+            let mut data: ReadBuf<'_> = ReadBuf::new(&mut self_mut.chunk);
+            let pin: Pin<&mut ReadHalf<TokioIo<Upgraded>>> = Pin::new(&mut self_mut.io);
+
+            match pin.poll_read(cx, &mut data) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(error)) => {
+                    self_mut.completed = true;
+                    return Poll::Ready(Some(DockerError::raise_upgraded_io_failed(&self_mut.url, error)));
+                }
+                Poll::Ready(Ok(())) if data.filled().is_empty() => self_mut.completed = true,
+                Poll::Ready(Ok(())) => {
+                    self_mut.buffer.append(data.filled());
+
+                    for item in extract(&mut self_mut.handler, &mut self_mut.buffer) {
+                        self_mut.completed = item.is_err();
+                        self_mut.prefetched.push_back(item);
+
+                        if self_mut.completed {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,83 +1,93 @@
 mod common;
+mod multiplexed;
+mod progress;
 
+use std::fmt;
+use std::future::Future;
+use std::path::Path;
 use std::pin::Pin;
 use std::str::from_utf8;
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use hyper::body::Bytes;
+use hyper::upgrade::Upgraded;
+use hyper_util::rt::TokioIo;
 
 use serde::Deserialize;
-use serde_json::from_slice;
-use tokio_stream::Stream;
+use tokio::io::ReadHalf;
+use tokio_stream::{Stream, StreamExt};
 
-use self::common::{DockerStream, DockerStreamBuffer, DockerStreamHandler};
+use self::common::{extract_json_lines, DockerIoStream, DockerStream, DockerStreamBatch};
+use self::common::{DockerStreamBuffer, DockerStreamHandler};
+use self::multiplexed::{MultiplexedFrame, MultiplexedParser};
+pub use self::progress::{ImagePullPhase, ImagePullProgress, ImagePullProgressStream, ImagePullSummary};
 
+use super::client::DockerClient;
 use super::error::{DockerError, DockerResult};
 use super::http::DockerResponse;
-use super::ErrorResponse;
+use super::{ErrorResponse, ExecInspect};
+use crate::tar::{TarExtraction, TarReader};
 
 #[derive(Debug)]
-struct ContainerLogsStreamHandler {}
+pub enum ContainerLogLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+impl ContainerLogLine {
+    pub fn text(&self) -> &str {
+        match self {
+            ContainerLogLine::Stdout(value) => value,
+            ContainerLogLine::Stderr(value) => value,
+        }
+    }
+
+    pub fn is_stderr(&self) -> bool {
+        matches!(self, ContainerLogLine::Stderr(_))
+    }
+}
+
+#[derive(Debug)]
+struct ContainerLogsStreamHandler {
+    parser: MultiplexedParser,
+}
 
 impl ContainerLogsStreamHandler {
     fn new() -> Self {
-        Self {}
+        Self {
+            parser: MultiplexedParser::new(),
+        }
     }
 }
 
 impl DockerStreamHandler for ContainerLogsStreamHandler {
-    type Item = String;
+    type Item = ContainerLogLine;
 
-    fn extract(&self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
-        let mut current: usize = 0;
-        let mut broken = false;
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
         let mut result = Vec::new();
 
-        let data = buffer.as_ref();
-        let length = data.len();
-
-        while !broken && current < length {
-            if current + 8 > length {
-                break;
-            }
-
-            let size = u32::from_be_bytes([
-                data[current + 4],
-                data[current + 5],
-                data[current + 6],
-                data[current + 7],
-            ]) as usize;
-
-            let start = current + 8;
-            let end = start + size;
-
-            if end > length {
-                break;
-            }
-
-            let message = match from_utf8(&data[start..end]) {
-                Err(error) => DockerError::raise_utf8_parsing_failed(error),
-                Ok(value) => Ok(value.to_string()),
+        for frame in self.parser.extract(buffer) {
+            let message = match (frame.kind, from_utf8(&frame.data)) {
+                (_, Err(error)) => DockerError::raise_utf8_parsing_failed(error),
+                (2, Ok(value)) => Ok(ContainerLogLine::Stderr(value.to_string())),
+                (_, Ok(value)) => Ok(ContainerLogLine::Stdout(value.to_string())),
             };
 
-            if let Err(_) = message {
-                broken = true;
-            }
-
+            let broken: bool = message.is_err();
             result.push(message);
-            current = end;
 
             if broken {
                 break;
             }
         }
 
-        if current > 0 {
-            buffer.consume(current);
-        }
-
         result
     }
+
+    fn validate(&self, buffer: &DockerStreamBuffer) -> Result<(), String> {
+        self.parser.validate(buffer)
+    }
 }
 
 #[derive(Debug)]
@@ -86,15 +96,21 @@ pub struct ContainerLogsStream {
 }
 
 impl ContainerLogsStream {
-    pub fn from(response: DockerResponse) -> Self {
+    pub(crate) fn from(response: DockerResponse) -> Self {
         Self {
             inner: DockerStream::from(ContainerLogsStreamHandler::new(), response),
         }
     }
+
+    pub fn batched(self, lines: usize, interval: Option<Duration>) -> ContainerLogsBatchStream {
+        ContainerLogsBatchStream {
+            inner: DockerStreamBatch::from(self.inner, lines, interval),
+        }
+    }
 }
 
 impl Stream for ContainerLogsStream {
-    type Item = DockerResult<String>;
+    type Item = DockerResult<ContainerLogLine>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let self_mut = self.get_mut();
@@ -106,55 +122,248 @@ impl Stream for ContainerLogsStream {
 }
 
 #[derive(Debug)]
-struct ImageCreateStreamHandler {}
+pub struct ContainerAttachStream {
+    inner: DockerIoStream<ContainerLogsStreamHandler>,
+}
 
-impl ImageCreateStreamHandler {
+impl ContainerAttachStream {
+    pub(crate) fn from(url: &str, io: ReadHalf<TokioIo<Upgraded>>) -> Self {
+        Self {
+            inner: DockerIoStream::from(ContainerLogsStreamHandler::new(), url, io),
+        }
+    }
+}
+
+impl Stream for ContainerAttachStream {
+    type Item = DockerResult<ContainerLogLine>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let pointer = &mut self_mut.inner;
+        let pin = Pin::new(pointer);
+
+        pin.poll_next(cx)
+    }
+}
+
+#[derive(Debug)]
+pub struct ContainerLogsBatchStream {
+    inner: DockerStreamBatch<ContainerLogsStreamHandler>,
+}
+
+impl Stream for ContainerLogsBatchStream {
+    type Item = DockerResult<Vec<ContainerLogLine>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let pointer = &mut self_mut.inner;
+        let pin = Pin::new(pointer);
+
+        pin.poll_next(cx)
+    }
+}
+
+#[derive(Debug)]
+struct ContainerArchiveStreamHandler {
+    parser: MultiplexedParser,
+}
+
+impl ContainerArchiveStreamHandler {
     fn new() -> Self {
-        Self {}
+        Self {
+            parser: MultiplexedParser::new(),
+        }
     }
 }
 
-impl DockerStreamHandler for ImageCreateStreamHandler {
-    type Item = ImageCreateStreamLine;
+impl DockerStreamHandler for ContainerArchiveStreamHandler {
+    type Item = MultiplexedFrame;
 
-    fn extract(&self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
-        let mut current: usize = 0;
-        let mut result: Vec<DockerResult<ImageCreateStreamItem>> = Vec::new();
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
+        self.parser.extract(buffer).into_iter().map(Ok).collect()
+    }
 
-        let data = buffer.as_ref();
-        let length = data.len();
+    fn validate(&self, buffer: &DockerStreamBuffer) -> Result<(), String> {
+        self.parser.validate(buffer)
+    }
+}
 
-        while current < length {
-            if current + 2 > length {
-                break;
+type ExecInspectTask = Pin<Box<dyn Future<Output = DockerResult<ExecInspect>> + Send>>;
+
+// only stdout carries the payload, stderr is kept aside to explain
+// a failure once the exit code of the exec is known at the very end
+pub struct ContainerArchiveStream {
+    exec_id: String,
+    client: Box<DockerClient>,
+    inner: Box<DockerStream<ContainerArchiveStreamHandler>>,
+    stderr: Vec<u8>,
+    inspect: Option<ExecInspectTask>,
+    finished: bool,
+}
+
+impl ContainerArchiveStream {
+    pub(crate) fn from(client: DockerClient, exec_id: String, response: DockerResponse) -> Self {
+        Self {
+            exec_id,
+            client: Box::new(client),
+            inner: Box::new(DockerStream::from(ContainerArchiveStreamHandler::new(), response)),
+            stderr: Vec::new(),
+            inspect: None,
+            finished: false,
+        }
+    }
+
+    pub fn exec_id(&self) -> &str {
+        &self.exec_id
+    }
+
+    fn verify(&self, outcome: DockerResult<ExecInspect>) -> Option<DockerResult<Bytes>> {
+        match outcome {
+            Err(error) => Some(Err(error)),
+            Ok(ExecInspect::Succeeded(value)) => match value.exit_code {
+                None | Some(0) => None,
+                Some(code) => Some(DockerError::raise_exec_failed(&self.exec_id, code, &self.stderr)),
+            },
+            Ok(ExecInspect::NoSuchInstance(value)) => {
+                Some(DockerError::raise_exec_unverified(&self.exec_id, value.message))
             }
+            Ok(ExecInspect::ServerError(value)) => {
+                Some(DockerError::raise_exec_unverified(&self.exec_id, value.message))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for ContainerArchiveStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContainerArchiveStream")
+            .field("exec_id", &self.exec_id)
+            .field("inner", &self.inner)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
 
-            for i in current..length - 1 {
-                if data[i] == 0x0d && data[i + 1] == 0x0a {
-                    let item: DockerResult<ImageCreateStreamItem> = {
-                        let data: &[u8] = &data[current..i];
-                        let data: Bytes = Bytes::from(data.to_vec());
+impl Stream for ContainerArchiveStream {
+    type Item = DockerResult<Bytes>;
 
-                        match from_slice(&data) {
-                            Ok(value) => Ok(value),
-                            Err(error) => DockerError::raise_deserialization_failed(None, error, data),
-                        }
-                    };
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+
+        loop {
+            if let Some(task) = &mut self_mut.inspect {
+                let outcome: DockerResult<ExecInspect> = match task.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(value) => value,
+                };
+
+                self_mut.inspect = None;
+                self_mut.finished = true;
 
-                    result.push(item);
-                    current = i + 2;
+                return Poll::Ready(self_mut.verify(outcome));
+            }
+
+            if self_mut.finished {
+                return Poll::Ready(None);
+            }
 
-                    continue;
+            match Pin::new(&mut self_mut.inner).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(Some(Ok(frame))) if frame.kind == 1 => {
+                    return Poll::Ready(Some(Ok(Bytes::from(frame.data))))
+                }
+                Poll::Ready(Some(Ok(frame))) => self_mut.stderr.extend_from_slice(&frame.data),
+                Poll::Ready(None) => {
+                    let client: DockerClient = DockerClient::clone(&self_mut.client);
+                    let id: String = self_mut.exec_id.clone();
+
+                    self_mut.inspect = Some(Box::pin(async move { client.exec_inspect(&id).await }));
                 }
             }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RawStreamHandler {}
+
+impl RawStreamHandler {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DockerStreamHandler for RawStreamHandler {
+    type Item = Bytes;
 
-            break;
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
+        let data: Bytes = Bytes::from(buffer.as_ref().to_vec());
+        let length: usize = data.len();
+
+        if length == 0 {
+            return Vec::new();
+        }
+
+        buffer.consume(length);
+        vec![Ok(data)]
+    }
+}
+
+#[derive(Debug)]
+pub struct ContainerDownloadStream {
+    inner: DockerStream<RawStreamHandler>,
+}
+
+impl ContainerDownloadStream {
+    pub(crate) fn from(response: DockerResponse) -> Self {
+        Self {
+            inner: DockerStream::from(RawStreamHandler::new(), response),
+        }
+    }
+
+    pub async fn extract(mut self, target: impl AsRef<Path>) -> DockerResult<TarExtraction> {
+        let mut reader: TarReader = TarReader::new(target);
+
+        while let Some(data) = self.next().await {
+            if let Err(error) = reader.write(data?.as_ref()).await {
+                return DockerError::raise_incoming_archive_failed(error);
+            }
         }
 
-        if current > 0 {
-            buffer.consume(current);
+        match reader.finish().await {
+            Ok(extraction) => Ok(extraction),
+            Err(error) => DockerError::raise_incoming_archive_failed(error),
         }
+    }
+}
+
+impl Stream for ContainerDownloadStream {
+    type Item = DockerResult<Bytes>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let pointer = &mut self_mut.inner;
+        let pin = Pin::new(pointer);
 
+        pin.poll_next(cx)
+    }
+}
+
+#[derive(Debug)]
+struct ImageCreateStreamHandler {}
+
+impl ImageCreateStreamHandler {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DockerStreamHandler for ImageCreateStreamHandler {
+    type Item = ImageCreateStreamLine;
+
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
+        let result: Vec<DockerResult<ImageCreateStreamItem>> = extract_json_lines(buffer);
         result.into_iter().map(ImageCreateStreamLine::from).collect()
     }
 }
@@ -171,6 +380,16 @@ pub struct ImageCreateStreamProgress {
     pub total: Option<u64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ImageCreateStreamAux {
+    #[serde(rename = "Tag")]
+    pub tag: Option<String>,
+    #[serde(rename = "Digest")]
+    pub digest: Option<String>,
+    #[serde(rename = "Size")]
+    pub size: Option<u64>,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ImageCreateStreamItem {
@@ -182,6 +401,7 @@ pub struct ImageCreateStreamItem {
     pub progress: Option<String>,
     #[serde(rename = "progressDetail")]
     pub progress_detail: Option<ImageCreateStreamProgress>,
+    pub aux: Option<ImageCreateStreamAux>,
 }
 
 #[derive(Debug)]
@@ -220,6 +440,16 @@ pub enum ImageCreateStreamLine {
 }
 
 impl ImageCreateStreamLine {
+    // the daemon reports the resolved digest as a status line after a pull
+    // and in the aux payload after a push
+    pub fn digest(&self) -> Option<&str> {
+        match self {
+            ImageCreateStreamLine::Info(info) => info.status.strip_prefix("Digest: "),
+            ImageCreateStreamLine::Raw(item) => item.aux.as_ref().and_then(|aux| aux.digest.as_deref()),
+            _ => None,
+        }
+    }
+
     fn from(item: DockerResult<ImageCreateStreamItem>) -> DockerResult<Self> {
         let item = match item {
             Ok(value) => value,
@@ -270,11 +500,15 @@ impl ImageCreateStreamLine {
 }
 
 impl ImageCreateStream {
-    pub fn from(response: DockerResponse) -> Self {
+    pub(crate) fn from(response: DockerResponse) -> Self {
         Self {
             inner: DockerStream::from(ImageCreateStreamHandler::new(), response),
         }
     }
+
+    pub fn aggregated(self) -> ImagePullProgressStream {
+        ImagePullProgressStream::from(self)
+    }
 }
 
 impl Stream for ImageCreateStream {
@@ -288,3 +522,97 @@ impl Stream for ImageCreateStream {
         pin.poll_next(cx)
     }
 }
+
+#[derive(Debug)]
+struct ImageBuildStreamHandler {}
+
+impl ImageBuildStreamHandler {
+    fn new() -> Self {
+        Self {}
+    }
+}
+
+impl DockerStreamHandler for ImageBuildStreamHandler {
+    type Item = ImageBuildStreamLine;
+
+    fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<DockerResult<Self::Item>> {
+        let result: Vec<DockerResult<ImageBuildStreamItem>> = extract_json_lines(buffer);
+        result.into_iter().map(ImageBuildStreamLine::from).collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageBuildStreamAux {
+    #[serde(rename = "ID")]
+    pub id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageBuildStreamItem {
+    pub stream: Option<String>,
+    pub status: Option<String>,
+    pub id: Option<String>,
+    pub error: Option<String>,
+    #[serde(rename = "errorDetail")]
+    pub error_detail: Option<ErrorResponse>,
+    pub progress: Option<String>,
+    #[serde(rename = "progressDetail")]
+    pub progress_detail: Option<ImageCreateStreamProgress>,
+    pub aux: Option<ImageBuildStreamAux>,
+}
+
+#[derive(Debug)]
+pub enum ImageBuildStreamLine {
+    Output(String),
+    Built(String),
+    Error(ImageCreateStreamLineError),
+    Raw(ImageBuildStreamItem),
+}
+
+impl ImageBuildStreamLine {
+    fn from(item: DockerResult<ImageBuildStreamItem>) -> DockerResult<Self> {
+        let item: ImageBuildStreamItem = item?;
+
+        if let (Some(message), Some(detail)) = (&item.error, &item.error_detail) {
+            return Ok(ImageBuildStreamLine::Error(ImageCreateStreamLineError {
+                message: message.clone(),
+                detail: detail.message.clone(),
+            }));
+        }
+
+        if let Some(stream) = &item.stream {
+            return Ok(ImageBuildStreamLine::Output(stream.clone()));
+        }
+
+        if let Some(ImageBuildStreamAux { id: Some(id) }) = &item.aux {
+            return Ok(ImageBuildStreamLine::Built(id.clone()));
+        }
+
+        Ok(ImageBuildStreamLine::Raw(item))
+    }
+}
+
+#[derive(Debug)]
+pub struct ImageBuildStream {
+    inner: DockerStream<ImageBuildStreamHandler>,
+}
+
+impl ImageBuildStream {
+    pub(crate) fn from(response: DockerResponse) -> Self {
+        Self {
+            inner: DockerStream::from(ImageBuildStreamHandler::new(), response),
+        }
+    }
+}
+
+impl Stream for ImageBuildStream {
+    type Item = DockerResult<ImageBuildStreamLine>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+        let pointer = &mut self_mut.inner;
+        let pin = Pin::new(pointer);
+
+        pin.poll_next(cx)
+    }
+}
@@ -0,0 +1,206 @@
+use std::mem;
+
+use super::common::DockerStreamBuffer;
+
+#[derive(Debug)]
+enum MultiplexedState {
+    AwaitingHeader,
+    AwaitingPayload(u8, usize),
+}
+
+#[derive(Debug)]
+pub struct MultiplexedFrame {
+    pub kind: u8,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct MultiplexedParser {
+    state: MultiplexedState,
+    payload: Vec<u8>,
+}
+
+impl MultiplexedParser {
+    pub fn new() -> Self {
+        Self {
+            state: MultiplexedState::AwaitingHeader,
+            payload: Vec::new(),
+        }
+    }
+
+    pub fn extract(&mut self, buffer: &mut DockerStreamBuffer) -> Vec<MultiplexedFrame> {
+        let mut current: usize = 0;
+        let mut frames: Vec<MultiplexedFrame> = Vec::new();
+
+        let data: &[u8] = buffer.as_ref();
+        let length: usize = data.len();
+
+        loop {
+            match self.state {
+                MultiplexedState::AwaitingHeader => {
+                    // the header stays in the buffer until all
+                    // 8 bytes arrived, even across many frames
+                    if current + 8 > length {
+                        break;
+                    }
+
+                    let kind: u8 = data[current];
+                    let size: usize = u32::from_be_bytes([
+                        data[current + 4],
+                        data[current + 5],
+                        data[current + 6],
+                        data[current + 7],
+                    ]) as usize;
+
+                    current += 8;
+                    self.state = MultiplexedState::AwaitingPayload(kind, size);
+                }
+                MultiplexedState::AwaitingPayload(kind, left) => {
+                    // the payload is moved out of the buffer as soon
+                    // as it arrives, so it may exceed buffer capacity
+                    let take: usize = std::cmp::min(left, length - current);
+
+                    self.payload.extend_from_slice(&data[current..current + take]);
+                    current += take;
+
+                    if take < left {
+                        self.state = MultiplexedState::AwaitingPayload(kind, left - take);
+                        break;
+                    }
+
+                    self.state = MultiplexedState::AwaitingHeader;
+                    frames.push(MultiplexedFrame {
+                        kind,
+                        data: mem::take(&mut self.payload),
+                    });
+                }
+            }
+        }
+
+        if current > 0 {
+            buffer.consume(current);
+        }
+
+        frames
+    }
+
+    pub fn validate(&self, buffer: &DockerStreamBuffer) -> Result<(), String> {
+        let data: &[u8] = buffer.as_ref();
+
+        match self.state {
+            MultiplexedState::AwaitingPayload(_, left) if !data.is_empty() => Err(format!(
+                "awaiting {left} payload bytes, but {} bytes were left unconsumed",
+                data.len()
+            )),
+            MultiplexedState::AwaitingPayload(..) => Ok(()),
+            // whatever the daemon sent, a complete header is always
+            // parsed, so only a partial one may be left behind
+            MultiplexedState::AwaitingHeader if data.len() >= 8 => Err(format!(
+                "awaiting header, but {} bytes were left unconsumed",
+                data.len()
+            )),
+            MultiplexedState::AwaitingHeader => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(kind: u8, payload: &[u8]) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![kind, 0, 0, 0];
+        data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        data.extend_from_slice(payload);
+        data
+    }
+
+    fn feed(chunks: &[&[u8]]) -> Vec<(u8, Vec<u8>)> {
+        let mut parser: MultiplexedParser = MultiplexedParser::new();
+        let mut buffer: DockerStreamBuffer = DockerStreamBuffer::new(16);
+        let mut frames: Vec<(u8, Vec<u8>)> = Vec::new();
+
+        for chunk in chunks {
+            buffer.append(chunk);
+
+            for frame in parser.extract(&mut buffer) {
+                frames.push((frame.kind, frame.data));
+            }
+
+            assert_eq!(parser.validate(&buffer), Ok(()));
+        }
+
+        frames
+    }
+
+    fn stream() -> (Vec<u8>, Vec<(u8, Vec<u8>)>) {
+        let expected: Vec<(u8, Vec<u8>)> = vec![
+            (1, b"hello\n".to_vec()),
+            (2, b"".to_vec()),
+            (2, b"oops\n".to_vec()),
+            (1, vec![0x00; 40]),
+        ];
+
+        let data: Vec<u8> = expected
+            .iter()
+            .flat_map(|(kind, payload)| frame(*kind, payload))
+            .collect();
+        (data, expected)
+    }
+
+    #[test]
+    fn parses_frames_split_at_every_offset() {
+        let (data, expected) = stream();
+
+        for offset in 0..=data.len() {
+            let (head, tail) = data.split_at(offset);
+            assert_eq!(feed(&[head, tail]), expected, "split at {offset}");
+        }
+    }
+
+    #[test]
+    fn parses_frames_split_at_every_pair_of_offsets() {
+        let (data, expected) = stream();
+
+        for first in 0..=data.len() {
+            for second in first..=data.len() {
+                let chunks: [&[u8]; 3] = [&data[..first], &data[first..second], &data[second..]];
+                assert_eq!(feed(&chunks), expected, "split at {first} and {second}");
+            }
+        }
+    }
+
+    #[test]
+    fn parses_frames_fed_byte_by_byte() {
+        let (data, expected) = stream();
+        let chunks: Vec<&[u8]> = data.chunks(1).collect();
+
+        assert_eq!(feed(&chunks), expected);
+    }
+
+    #[test]
+    fn parses_zero_length_frames() {
+        let data: Vec<u8> = [frame(1, b""), frame(2, b""), frame(1, b"")].concat();
+        let expected: Vec<(u8, Vec<u8>)> = vec![(1, vec![]), (2, vec![]), (1, vec![])];
+
+        assert_eq!(feed(&[&data]), expected);
+        assert_eq!(feed(&[&data[..8], &data[8..]]), expected);
+    }
+
+    #[test]
+    fn parses_back_to_back_frames_in_one_chunk() {
+        let (data, expected) = stream();
+        assert_eq!(feed(&[&data]), expected);
+    }
+
+    #[test]
+    fn keeps_partial_header_in_buffer() {
+        let mut parser: MultiplexedParser = MultiplexedParser::new();
+        let mut buffer: DockerStreamBuffer = DockerStreamBuffer::new(16);
+
+        buffer.append(&frame(1, b"abc")[..5]);
+
+        assert!(parser.extract(&mut buffer).is_empty());
+        assert_eq!(buffer.len(), 5);
+    }
+}
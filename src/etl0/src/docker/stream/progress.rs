@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio_stream::Stream;
+
+use super::{ImageCreateStream, ImageCreateStreamLine};
+use crate::docker::error::{DockerError, DockerResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImagePullPhase {
+    Resolving,
+    Downloading,
+    Extracting,
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ImagePullLayer {
+    Waiting,
+    Downloading(u64, u64),
+    Downloaded(u64),
+    Extracting(u64),
+    Completed(u64),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImagePullSummary {
+    pub phase: ImagePullPhase,
+    pub layers_done: usize,
+    pub layers_total: usize,
+    pub bytes_downloaded: u64,
+    pub bytes_total: u64,
+}
+
+#[derive(Debug, Default)]
+pub struct ImagePullProgress {
+    order: Vec<String>,
+    layers: HashMap<String, ImagePullLayer>,
+    completed: bool,
+    failure: Option<String>,
+}
+
+impl ImagePullProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // folds one line into the per-layer state and tells
+    // whether the consolidated summary might have changed
+    pub fn update(&mut self, line: &ImageCreateStreamLine) -> bool {
+        let (id, layer) = match line {
+            ImageCreateStreamLine::Progress(progress) => {
+                let known: u64 = self.size(&progress.id);
+
+                match progress.status.as_str() {
+                    "Downloading" => (
+                        &progress.id,
+                        ImagePullLayer::Downloading(progress.current, progress.total),
+                    ),
+                    "Extracting" => (&progress.id, ImagePullLayer::Extracting(known.max(progress.total))),
+                    _ => return false,
+                }
+            }
+            ImageCreateStreamLine::Status(status) => {
+                let known: u64 = self.size(&status.id);
+
+                match status.status.as_str() {
+                    "Pulling fs layer" | "Waiting" => (&status.id, ImagePullLayer::Waiting),
+                    "Verifying Checksum" | "Download complete" => (&status.id, ImagePullLayer::Downloaded(known)),
+                    "Pull complete" | "Already exists" => (&status.id, ImagePullLayer::Completed(known)),
+                    _ => return false,
+                }
+            }
+            ImageCreateStreamLine::Info(info) if info.status.starts_with("Status: ") => {
+                self.completed = true;
+                return true;
+            }
+            ImageCreateStreamLine::Error(error) => {
+                self.failure = Some(error.message.clone());
+                return true;
+            }
+            _ => return false,
+        };
+
+        if !self.layers.contains_key(id) {
+            self.order.push(id.clone());
+        }
+
+        self.layers.insert(id.clone(), layer) != Some(layer)
+    }
+
+    // the daemon reports a failed pull as an error line
+    // in the middle of an otherwise successful response
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.as_deref()
+    }
+
+    fn size(&self, id: &str) -> u64 {
+        match self.layers.get(id) {
+            Some(ImagePullLayer::Downloading(_, total)) => *total,
+            Some(ImagePullLayer::Downloaded(total)) => *total,
+            Some(ImagePullLayer::Extracting(total)) => *total,
+            Some(ImagePullLayer::Completed(total)) => *total,
+            Some(ImagePullLayer::Waiting) | None => 0,
+        }
+    }
+
+    pub fn summary(&self) -> ImagePullSummary {
+        let mut summary = ImagePullSummary {
+            phase: ImagePullPhase::Resolving,
+            layers_done: 0,
+            layers_total: self.order.len(),
+            bytes_downloaded: 0,
+            bytes_total: 0,
+        };
+
+        let mut downloading: bool = false;
+        let mut extracting: bool = false;
+
+        for layer in self.layers.values() {
+            match *layer {
+                ImagePullLayer::Waiting => downloading = true,
+                ImagePullLayer::Downloading(current, total) => {
+                    downloading = true;
+                    summary.bytes_downloaded += current;
+                    summary.bytes_total += total;
+                }
+                ImagePullLayer::Downloaded(total) | ImagePullLayer::Extracting(total) => {
+                    extracting = true;
+                    summary.bytes_downloaded += total;
+                    summary.bytes_total += total;
+                }
+                ImagePullLayer::Completed(total) => {
+                    summary.layers_done += 1;
+                    summary.bytes_downloaded += total;
+                    summary.bytes_total += total;
+                }
+            }
+        }
+
+        summary.phase = match (self.completed, downloading, extracting) {
+            _ if self.failure.is_some() => ImagePullPhase::Failed,
+            (true, _, _) => ImagePullPhase::Completed,
+            (false, true, _) => ImagePullPhase::Downloading,
+            (false, false, true) => ImagePullPhase::Extracting,
+            (false, false, false) if summary.layers_total > 0 => ImagePullPhase::Completed,
+            (false, false, false) => ImagePullPhase::Resolving,
+        };
+
+        summary
+    }
+}
+
+#[derive(Debug)]
+pub struct ImagePullProgressStream {
+    inner: ImageCreateStream,
+    progress: ImagePullProgress,
+    last: Option<ImagePullSummary>,
+}
+
+impl ImagePullProgressStream {
+    pub(crate) fn from(inner: ImageCreateStream) -> Self {
+        Self {
+            inner,
+            progress: ImagePullProgress::new(),
+            last: None,
+        }
+    }
+}
+
+impl Stream for ImagePullProgressStream {
+    type Item = DockerResult<ImagePullSummary>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let self_mut = self.get_mut();
+
+        loop {
+            let line: ImageCreateStreamLine = match Pin::new(&mut self_mut.inner).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(error))) => return Poll::Ready(Some(Err(error))),
+                Poll::Ready(Some(Ok(line))) => line,
+            };
+
+            if !self_mut.progress.update(&line) {
+                continue;
+            }
+
+            if let ImageCreateStreamLine::Error(error) = &line {
+                return Poll::Ready(Some(DockerError::raise_pull_failed(&error.message)));
+            }
+
+            // consecutive lines often leave the totals untouched,
+            // only real changes are worth redrawing a progress bar
+            let summary: ImagePullSummary = self_mut.progress.summary();
+            if self_mut.last.as_ref() != Some(&summary) {
+                self_mut.last = Some(summary.clone());
+                return Poll::Ready(Some(Ok(summary)));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::docker::stream::{ImageCreateStreamLineError, ImageCreateStreamLineStatus};
+
+    #[test]
+    fn reports_failed_phase_after_error_line() {
+        let mut progress: ImagePullProgress = ImagePullProgress::new();
+
+        let status: ImageCreateStreamLine = ImageCreateStreamLine::Status(ImageCreateStreamLineStatus {
+            id: String::from("a1b2"),
+            status: String::from("Pulling fs layer"),
+        });
+
+        let error: ImageCreateStreamLine = ImageCreateStreamLine::Error(ImageCreateStreamLineError {
+            message: String::from("unauthorized: authentication required"),
+            detail: String::from("{}"),
+        });
+
+        assert!(progress.update(&status));
+        assert_eq!(progress.summary().phase, ImagePullPhase::Downloading);
+
+        assert!(progress.update(&error));
+        assert_eq!(progress.summary().phase, ImagePullPhase::Failed);
+        assert_eq!(progress.failure(), Some("unauthorized: authentication required"));
+    }
+}
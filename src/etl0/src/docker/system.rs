@@ -0,0 +1,275 @@
+use std::io::ErrorKind;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use serde::Deserialize;
+
+use super::client::DockerClient;
+use super::endpoint::DockerEndpoint;
+use super::error::{DockerError, DockerResult};
+use super::http::DockerConnection;
+use super::types::{ContainerInfo, ErrorResponse, ImageInfo};
+use super::volumes::VolumeInfo;
+
+#[derive(Debug, Deserialize)]
+pub struct SystemInfoResponse {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "ServerVersion")]
+    pub server_version: String,
+    #[serde(rename = "Containers")]
+    pub containers: u64,
+    #[serde(rename = "ContainersRunning")]
+    pub containers_running: u64,
+    #[serde(rename = "Images")]
+    pub images: u64,
+    #[serde(rename = "Driver")]
+    pub driver: String,
+    #[serde(rename = "DockerRootDir")]
+    pub docker_root_dir: String,
+    #[serde(rename = "NCPU")]
+    pub ncpu: u64,
+    #[serde(rename = "MemTotal")]
+    pub mem_total: u64,
+    #[serde(rename = "OperatingSystem")]
+    pub operating_system: String,
+    #[serde(rename = "OSType")]
+    pub os_type: String,
+    #[serde(rename = "Architecture")]
+    pub architecture: String,
+    #[serde(rename = "KernelVersion")]
+    pub kernel_version: String,
+    #[serde(rename = "SecurityOptions")]
+    pub security_options: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemVersionResponse {
+    #[serde(rename = "Version")]
+    pub version: String,
+    #[serde(rename = "ApiVersion")]
+    pub api_version: String,
+    #[serde(rename = "MinAPIVersion")]
+    pub min_api_version: Option<String>,
+    #[serde(rename = "GitCommit")]
+    pub git_commit: String,
+    #[serde(rename = "GoVersion")]
+    pub go_version: String,
+    #[serde(rename = "Os")]
+    pub os: String,
+    #[serde(rename = "Arch")]
+    pub arch: String,
+    #[serde(rename = "KernelVersion")]
+    pub kernel_version: Option<String>,
+}
+
+impl SystemVersionResponse {
+    pub fn supports(&self, version: &str) -> bool {
+        let version: Option<(u32, u32)> = parse_api_version(version);
+        let maximum: Option<(u32, u32)> = parse_api_version(&self.api_version);
+        let minimum: Option<(u32, u32)> = match &self.min_api_version {
+            Some(value) => parse_api_version(value),
+            None => Some((0, 0)),
+        };
+
+        match (version, minimum, maximum) {
+            (Some(version), Some(minimum), Some(maximum)) => minimum <= version && version <= maximum,
+            _ => false,
+        }
+    }
+}
+
+pub(crate) fn parse_api_version(value: &str) -> Option<(u32, u32)> {
+    let (major, minor) = value.trim_start_matches('v').split_once('.')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemBuildCacheInfo {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "Type")]
+    pub kind: String,
+    #[serde(rename = "Size")]
+    pub size: i64,
+    #[serde(rename = "InUse")]
+    pub in_use: bool,
+    #[serde(rename = "Shared")]
+    pub shared: bool,
+    #[serde(rename = "LastUsedAt")]
+    pub last_used_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemDfResponse {
+    #[serde(rename = "LayersSize")]
+    pub layers_size: Option<i64>,
+    #[serde(rename = "Images")]
+    pub images: Option<Vec<ImageInfo>>,
+    #[serde(rename = "Containers")]
+    pub containers: Option<Vec<ContainerInfo>>,
+    #[serde(rename = "Volumes")]
+    pub volumes: Option<Vec<VolumeInfo>>,
+    #[serde(rename = "BuildCache")]
+    pub build_cache: Option<Vec<SystemBuildCacheInfo>>,
+}
+
+impl SystemDfResponse {
+    pub fn images_size(&self) -> i64 {
+        self.images.iter().flatten().map(|image| image.size).sum()
+    }
+
+    pub fn containers_size(&self) -> i64 {
+        self.containers
+            .iter()
+            .flatten()
+            .filter_map(|container| container.size_rw)
+            .sum()
+    }
+
+    pub fn volumes_size(&self) -> i64 {
+        let usage = self
+            .volumes
+            .iter()
+            .flatten()
+            .filter_map(|volume| volume.usage_data.as_ref());
+        usage.map(|usage| usage.size.max(0)).sum()
+    }
+
+    pub fn build_cache_size(&self) -> i64 {
+        self.build_cache.iter().flatten().map(|entry| entry.size).sum()
+    }
+}
+
+#[derive(Debug)]
+pub enum SystemDf {
+    Succeeded(SystemDfResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum SystemPing {
+    Succeeded(String),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum SystemInfo {
+    Succeeded(Box<SystemInfoResponse>),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum SystemVersion {
+    Succeeded(SystemVersionResponse),
+    ServerError(ErrorResponse),
+}
+
+impl DockerClient {
+    pub async fn ping(&self) -> DockerResult<SystemPing> {
+        let url: String = String::from("/_ping");
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(value) => Ok(SystemPing::Succeeded(String::from_utf8_lossy(&value).to_string())),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(SystemPing::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    // translates the usual ways of a broken setup into errors
+    // telling what to fix before any pipeline work is started
+    pub async fn check(&self) -> DockerResult<()> {
+        if let DockerEndpoint::Unix(socket) = &self.endpoint {
+            if let Err(error) = tokio::fs::metadata(socket).await {
+                return match error.kind() {
+                    ErrorKind::NotFound => DockerError::raise_socket_not_found(socket),
+                    ErrorKind::PermissionDenied => DockerError::raise_socket_permission_denied(socket),
+                    _ => DockerError::raise_unix_socket_connect(socket, error),
+                };
+            }
+        }
+
+        let endpoint: String = self.endpoint.to_string();
+
+        match self.ping().await {
+            Ok(SystemPing::Succeeded(value)) if value == "OK" => Ok(()),
+            Ok(SystemPing::Succeeded(value)) => DockerError::raise_ping_failed(&endpoint, value),
+            Ok(SystemPing::ServerError(value)) => DockerError::raise_ping_failed(&endpoint, value.message),
+            Err(DockerError::UnixSocketConnect(socket, error)) => match error.kind() {
+                ErrorKind::PermissionDenied => DockerError::raise_socket_permission_denied(&socket),
+                ErrorKind::ConnectionRefused => DockerError::raise_socket_refused(&socket),
+                ErrorKind::NotFound => DockerError::raise_socket_not_found(&socket),
+                _ => DockerError::raise_unix_socket_connect(&socket, error),
+            },
+            Err(error) => Err(error),
+        }
+    }
+
+    pub async fn system_df(&self) -> DockerResult<SystemDf> {
+        let url: String = format!("{}/system/df", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(SystemDf::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(SystemDf::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn system_info(&self) -> DockerResult<SystemInfo> {
+        let url: String = format!("{}/info", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(SystemInfo::Succeeded(Box::new(value))),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(SystemInfo::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn system_version(&self) -> DockerResult<SystemVersion> {
+        let url: String = format!("{}/version", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(SystemVersion::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(SystemVersion::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio_rustls::rustls::crypto::ring::default_provider;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer, ServerName};
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use super::error::{DockerError, DockerResult};
+
+// client side of a daemon started with --tlsverify, laid out
+// like DOCKER_CERT_PATH with ca.pem, cert.pem and key.pem
+#[derive(Debug, Clone)]
+pub struct DockerTls {
+    pub path: PathBuf,
+    config: Arc<ClientConfig>,
+}
+
+impl DockerTls {
+    pub fn load(path: impl AsRef<Path>) -> DockerResult<Self> {
+        let path: &Path = path.as_ref();
+        let ca: PathBuf = path.join("ca.pem");
+        let cert: PathBuf = path.join("cert.pem");
+        let key: PathBuf = path.join("key.pem");
+
+        let mut roots: RootCertStore = RootCertStore::empty();
+        for certificate in Self::certificates(&ca)? {
+            if let Err(error) = roots.add(certificate) {
+                return DockerError::raise_tls_config_failed(&ca, error.to_string());
+            }
+        }
+
+        let builder = match ClientConfig::builder_with_provider(Arc::new(default_provider()))
+            .with_safe_default_protocol_versions()
+        {
+            Err(error) => return DockerError::raise_tls_config_failed(path, error.to_string()),
+            Ok(value) => value.with_root_certificates(roots),
+        };
+
+        // a daemon may accept clients without certificates,
+        // so the pair is only required when one of them is present
+        let config: ClientConfig = match (cert.exists(), key.exists()) {
+            (false, false) => builder.with_no_client_auth(),
+            _ => {
+                let certificates: Vec<CertificateDer<'static>> = Self::certificates(&cert)?;
+                let key: PrivateKeyDer<'static> = match PrivateKeyDer::from_pem_file(&key) {
+                    Err(error) => return DockerError::raise_tls_config_failed(&key, error.to_string()),
+                    Ok(value) => value,
+                };
+
+                match builder.with_client_auth_cert(certificates, key) {
+                    Err(error) => return DockerError::raise_tls_config_failed(&cert, error.to_string()),
+                    Ok(value) => value,
+                }
+            }
+        };
+
+        Ok(Self {
+            path: path.to_owned(),
+            config: Arc::new(config),
+        })
+    }
+
+    fn certificates(path: &Path) -> DockerResult<Vec<CertificateDer<'static>>> {
+        let iterator = match CertificateDer::pem_file_iter(path) {
+            Err(error) => return DockerError::raise_tls_config_failed(path, error.to_string()),
+            Ok(value) => value,
+        };
+
+        let mut certificates: Vec<CertificateDer<'static>> = Vec::new();
+        for certificate in iterator {
+            match certificate {
+                Err(error) => return DockerError::raise_tls_config_failed(path, error.to_string()),
+                Ok(value) => certificates.push(value),
+            }
+        }
+
+        match certificates.is_empty() {
+            true => DockerError::raise_tls_config_failed(path, "no certificates found".to_owned()),
+            false => Ok(certificates),
+        }
+    }
+
+    pub(crate) fn connector(&self) -> TlsConnector {
+        TlsConnector::from(self.config.clone())
+    }
+
+    pub(crate) fn server_name(address: &str) -> DockerResult<ServerName<'static>> {
+        let host: &str = match address.rsplit_once(':') {
+            Some((host, _)) => host.trim_start_matches('[').trim_end_matches(']'),
+            None => address,
+        };
+
+        match ServerName::try_from(host.to_owned()) {
+            Err(error) => DockerError::raise_endpoint_invalid(address, &error.to_string()),
+            Ok(value) => Ok(value),
+        }
+    }
+}
@@ -1,6 +1,27 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::auth::RegistryAuth;
+use super::query::{encode, encode_json};
+use super::stdin::ContainerStdin;
+pub use super::stream::{ContainerArchiveStream, ContainerAttachStream, ContainerDownloadStream};
+pub use super::stream::{ContainerLogLine, ContainerLogsBatchStream, ContainerLogsStream, ImageCreateStream};
+pub use super::stream::{ImageBuildStream, ImageBuildStreamAux, ImageBuildStreamItem, ImageBuildStreamLine};
+pub use super::stream::{
+    ImageCreateStreamAux, ImageCreateStreamItem, ImageCreateStreamLine, ImageCreateStreamProgress,
+};
+pub use super::stream::{ImageCreateStreamLineError, ImageCreateStreamLineInfo};
+pub use super::stream::{ImageCreateStreamLineProgress, ImageCreateStreamLineStatus};
+pub use super::stream::{ImagePullPhase, ImagePullProgress, ImagePullProgressStream, ImagePullSummary};
 
-pub use super::stream::{ContainerLogsStream, ImageCreateStream};
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DockerResponseLimits {
+    pub timeout: Option<Duration>,
+    pub max_size: Option<usize>,
+}
 
 #[derive(Debug, Deserialize)]
 pub struct ContainerInfo {
@@ -16,6 +37,105 @@ pub struct ContainerInfo {
     pub command: String,
     #[serde(rename = "Status")]
     pub status: String,
+    #[serde(rename = "SizeRw")]
+    pub size_rw: Option<i64>,
+    #[serde(rename = "SizeRootFs")]
+    pub size_root_fs: Option<i64>,
+    #[serde(rename = "Labels")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContainerListOptions {
+    all: bool,
+    limit: Option<u32>,
+    size: bool,
+    labels: Vec<String>,
+    statuses: Vec<String>,
+    names: Vec<String>,
+    since: Option<String>,
+    before: Option<String>,
+}
+
+impl ContainerListOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn all(self, value: bool) -> Self {
+        Self { all: value, ..self }
+    }
+
+    pub fn limit(self, value: u32) -> Self {
+        Self {
+            limit: Some(value),
+            ..self
+        }
+    }
+
+    pub fn size(self, value: bool) -> Self {
+        Self { size: value, ..self }
+    }
+
+    pub fn label(mut self, value: &str) -> Self {
+        self.labels.push(value.to_owned());
+        self
+    }
+
+    pub fn status(mut self, value: &str) -> Self {
+        self.statuses.push(value.to_owned());
+        self
+    }
+
+    pub fn name(mut self, value: &str) -> Self {
+        self.names.push(value.to_owned());
+        self
+    }
+
+    pub fn since(self, value: &str) -> Self {
+        Self {
+            since: Some(value.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn before(self, value: &str) -> Self {
+        Self {
+            before: Some(value.to_owned()),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_query(&self) -> String {
+        let mut query: Vec<String> = vec![format!("all={}", self.all), format!("size={}", self.size)];
+        let mut filters: Value = json!({});
+
+        if let Some(value) = self.limit {
+            query.push(format!("limit={value}"));
+        }
+
+        for (key, values) in [
+            ("label", &self.labels),
+            ("status", &self.statuses),
+            ("name", &self.names),
+        ] {
+            if !values.is_empty() {
+                filters[key] = json!(values);
+            }
+        }
+
+        for (key, value) in [("since", &self.since), ("before", &self.before)] {
+            if let Some(value) = value {
+                filters[key] = json!([value]);
+            }
+        }
+
+        if filters.as_object().is_some_and(|value| !value.is_empty()) {
+            query.push(format!("filters={}", encode_json(&filters)));
+        }
+
+        query.join("&")
+    }
 }
 
 #[derive(Debug)]
@@ -33,6 +153,238 @@ pub struct ContainerCreateResponse {
     pub warnings: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ImageInfo {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(rename = "ParentId")]
+    pub parent_id: String,
+    #[serde(rename = "RepoTags")]
+    pub repo_tags: Option<Vec<String>>,
+    #[serde(rename = "RepoDigests")]
+    pub repo_digests: Option<Vec<String>>,
+    #[serde(rename = "Created")]
+    pub created: i64,
+    #[serde(rename = "Size")]
+    pub size: i64,
+    #[serde(rename = "Labels")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImageListOptions {
+    all: bool,
+    dangling: Option<bool>,
+    labels: Vec<String>,
+    references: Vec<String>,
+}
+
+impl ImageListOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn all(self, value: bool) -> Self {
+        Self { all: value, ..self }
+    }
+
+    pub fn dangling(self, value: bool) -> Self {
+        Self {
+            dangling: Some(value),
+            ..self
+        }
+    }
+
+    pub fn label(mut self, value: &str) -> Self {
+        self.labels.push(value.to_owned());
+        self
+    }
+
+    pub fn reference(mut self, value: &str) -> Self {
+        self.references.push(value.to_owned());
+        self
+    }
+
+    pub(crate) fn to_query(&self) -> String {
+        let mut query: Vec<String> = vec![format!("all={}", self.all)];
+        let mut filters: Value = json!({});
+
+        if let Some(value) = self.dangling {
+            filters["dangling"] = json!([value.to_string()]);
+        }
+
+        for (key, values) in [("label", &self.labels), ("reference", &self.references)] {
+            if !values.is_empty() {
+                filters[key] = json!(values);
+            }
+        }
+
+        if filters.as_object().is_some_and(|value| !value.is_empty()) {
+            query.push(format!("filters={}", encode_json(&filters)));
+        }
+
+        query.join("&")
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageList {
+    Succeeded(Vec<ImageInfo>),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImageDeleteItem {
+    #[serde(rename = "Untagged")]
+    pub untagged: Option<String>,
+    #[serde(rename = "Deleted")]
+    pub deleted: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum ImageRemove {
+    Succeeded(Vec<ImageDeleteItem>),
+    NoSuchImage(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Default)]
+pub struct ImagePruneFilters<'a> {
+    dangling: Option<bool>,
+    labels: Vec<&'a str>,
+    until: Option<&'a str>,
+}
+
+impl<'a> ImagePruneFilters<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dangling(self, value: bool) -> Self {
+        Self {
+            dangling: Some(value),
+            ..self
+        }
+    }
+
+    pub fn label(mut self, value: &'a str) -> Self {
+        self.labels.push(value);
+        self
+    }
+
+    pub fn until(self, value: &'a str) -> Self {
+        Self {
+            until: Some(value),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut payload: Value = json!({"label": self.labels});
+
+        if let Some(dangling) = self.dangling {
+            payload["dangling"] = json!([dangling.to_string()]);
+        }
+
+        if let Some(until) = self.until {
+            payload["until"] = json!([until]);
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImagePruneResponse {
+    #[serde(rename = "ImagesDeleted")]
+    pub images_deleted: Option<Vec<ImageDeleteItem>>,
+    #[serde(rename = "SpaceReclaimed")]
+    pub space_reclaimed: u64,
+}
+
+#[derive(Debug)]
+pub enum ImagePrune {
+    Succeeded(ImagePruneResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ImageReference<'a> {
+    pub name: &'a str,
+    pub tag: Option<&'a str>,
+    pub digest: Option<&'a str>,
+    pub platform: Option<&'a str>,
+}
+
+impl<'a> ImageReference<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            tag: None,
+            digest: None,
+            platform: None,
+        }
+    }
+
+    pub fn parse(value: &'a str) -> Self {
+        let (value, digest) = match value.split_once('@') {
+            Some((name, digest)) => (name, Some(digest)),
+            None => (value, None),
+        };
+
+        let slash: usize = value.rfind('/').map(|index| index + 1).unwrap_or(0);
+        let (name, tag) = match value[slash..].rfind(':') {
+            Some(index) => (&value[..slash + index], Some(&value[slash + index + 1..])),
+            None => (value, None),
+        };
+
+        Self {
+            name,
+            tag,
+            digest,
+            platform: None,
+        }
+    }
+
+    pub fn tag(self, value: &'a str) -> Self {
+        Self {
+            tag: Some(value),
+            ..self
+        }
+    }
+
+    pub fn digest(self, value: &'a str) -> Self {
+        Self {
+            digest: Some(value),
+            ..self
+        }
+    }
+
+    pub fn platform(self, value: &'a str) -> Self {
+        Self {
+            platform: Some(value),
+            ..self
+        }
+    }
+
+    pub fn to_query(&self) -> String {
+        let tag: &str = match (self.digest, self.tag) {
+            (Some(digest), _) => digest,
+            (None, Some(tag)) => tag,
+            (None, None) => "latest",
+        };
+
+        let mut query: String = format!("fromImage={}&tag={}", encode(self.name), encode(tag));
+
+        if let Some(platform) = self.platform {
+            query.push_str(&format!("&platform={}", encode(platform)));
+        }
+
+        query
+    }
+}
+
 #[derive(Debug)]
 pub enum ImageCreate {
     Succeeded(ImageCreateStream),
@@ -40,6 +392,154 @@ pub enum ImageCreate {
     ServerError(ErrorResponse),
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct ImageBuildSpec<'a> {
+    pub tags: Vec<&'a str>,
+    pub dockerfile: Option<&'a str>,
+    pub build_args: HashMap<&'a str, &'a str>,
+    pub labels: HashMap<&'a str, &'a str>,
+    pub no_cache: bool,
+    pub pull: bool,
+    pub platform: Option<&'a str>,
+    pub registry_auths: HashMap<&'a str, RegistryAuth>,
+}
+
+impl<'a> ImageBuildSpec<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tag(mut self, value: &'a str) -> Self {
+        self.tags.push(value);
+        self
+    }
+
+    pub fn dockerfile(self, value: &'a str) -> Self {
+        Self {
+            dockerfile: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build_arg(mut self, key: &'a str, value: &'a str) -> Self {
+        self.build_args.insert(key, value);
+        self
+    }
+
+    pub fn label(mut self, key: &'a str, value: &'a str) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    pub fn no_cache(self, value: bool) -> Self {
+        Self {
+            no_cache: value,
+            ..self
+        }
+    }
+
+    pub fn pull(self, value: bool) -> Self {
+        Self { pull: value, ..self }
+    }
+
+    pub fn platform(self, value: &'a str) -> Self {
+        Self {
+            platform: Some(value),
+            ..self
+        }
+    }
+
+    pub fn registry_auth(mut self, registry: &'a str, auth: RegistryAuth) -> Self {
+        self.registry_auths.insert(registry, auth);
+        self
+    }
+
+    pub(crate) fn to_query(&self) -> String {
+        let mut query: Vec<String> = vec![format!("nocache={}", self.no_cache), format!("pull={}", self.pull)];
+
+        for tag in self.tags.iter() {
+            query.push(format!("t={}", encode(tag)));
+        }
+
+        if let Some(dockerfile) = self.dockerfile {
+            query.push(format!("dockerfile={}", encode(dockerfile)));
+        }
+
+        if let Some(platform) = self.platform {
+            query.push(format!("platform={}", encode(platform)));
+        }
+
+        if !self.build_args.is_empty() {
+            query.push(format!("buildargs={}", encode_json(&json!(self.build_args))));
+        }
+
+        if !self.labels.is_empty() {
+            query.push(format!("labels={}", encode_json(&json!(self.labels))));
+        }
+
+        query.join("&")
+    }
+}
+
+#[derive(Debug)]
+pub enum ImageBuild {
+    Succeeded(ImageBuildStream),
+    BadParameter(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ImageGet {
+    Succeeded(ContainerDownloadStream),
+    NoSuchImage(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ImageLoad {
+    Succeeded(ImageBuildStream),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ImagePush {
+    Succeeded(ImageCreateStream),
+    NoSuchImage(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Clone)]
+pub struct ImagePullRetry {
+    pub attempts: u32,
+    pub backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ImagePullRetry {
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            backoff: Duration::from_secs(2),
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ImagePullRetry {
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor: u32 = 2u32.saturating_pow(attempt);
+        std::cmp::min(self.backoff.saturating_mul(factor), self.max_backoff)
+    }
+}
+
+#[derive(Debug)]
+pub enum ImagePull {
+    Succeeded(Option<String>),
+    NoReadAccess(ErrorResponse),
+    ServerError(ErrorResponse),
+    Failed(ImageCreateStreamLineError),
+}
+
 #[derive(Debug)]
 pub enum ContainerCreate {
     Succeeded(ContainerCreateResponse),
@@ -49,16 +549,343 @@ pub enum ContainerCreate {
     ServerError(ErrorResponse),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+pub enum ContainerMount<'a> {
+    Bind(&'a str, &'a str, bool),
+    Volume(&'a str, &'a str, bool),
+    Tmpfs(&'a str, Option<u64>),
+}
+
+impl ContainerMount<'_> {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ContainerMount::Bind(source, target, read_only) => {
+                json!({"Type": "bind", "Source": source, "Target": target, "ReadOnly": read_only})
+            }
+            ContainerMount::Volume(source, target, read_only) => {
+                json!({"Type": "volume", "Source": source, "Target": target, "ReadOnly": read_only})
+            }
+            ContainerMount::Tmpfs(target, None) => json!({"Type": "tmpfs", "Target": target}),
+            ContainerMount::Tmpfs(target, Some(size)) => {
+                json!({"Type": "tmpfs", "Target": target, "TmpfsOptions": {"SizeBytes": size}})
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerPortBinding<'a> {
+    pub container_port: &'a str,
+    pub host_ip: Option<&'a str>,
+    pub host_port: &'a str,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContainerDevice<'a> {
+    pub host_path: &'a str,
+    pub container_path: &'a str,
+    pub permissions: &'a str,
+}
+
+impl ContainerDevice<'_> {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "PathOnHost": self.host_path,
+            "PathInContainer": self.container_path,
+            "CgroupPermissions": self.permissions,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContainerHostConfig<'a> {
+    pub binds: Vec<&'a str>,
+    pub mounts: Vec<ContainerMount<'a>>,
+    pub port_bindings: Vec<ContainerPortBinding<'a>>,
+    pub network_mode: Option<&'a str>,
+    pub restart_policy: Option<ContainerRestartPolicy>,
+    pub auto_remove: bool,
+    pub devices: Vec<ContainerDevice<'a>>,
+    pub privileged: bool,
+    pub shm_size: Option<u64>,
+}
+
+impl<'a> ContainerHostConfig<'a> {
+    pub fn bind(mut self, value: &'a str) -> Self {
+        self.binds.push(value);
+        self
+    }
+
+    pub fn mount(mut self, value: ContainerMount<'a>) -> Self {
+        self.mounts.push(value);
+        self
+    }
+
+    pub fn port(mut self, value: ContainerPortBinding<'a>) -> Self {
+        self.port_bindings.push(value);
+        self
+    }
+
+    pub fn network_mode(self, value: &'a str) -> Self {
+        Self {
+            network_mode: Some(value),
+            ..self
+        }
+    }
+
+    pub fn restart_policy(self, value: ContainerRestartPolicy) -> Self {
+        Self {
+            restart_policy: Some(value),
+            ..self
+        }
+    }
+
+    pub fn auto_remove(self, value: bool) -> Self {
+        Self {
+            auto_remove: value,
+            ..self
+        }
+    }
+
+    pub fn device(mut self, value: ContainerDevice<'a>) -> Self {
+        self.devices.push(value);
+        self
+    }
+
+    pub fn privileged(self, value: bool) -> Self {
+        Self {
+            privileged: value,
+            ..self
+        }
+    }
+
+    pub fn shm_size(self, value: u64) -> Self {
+        Self {
+            shm_size: Some(value),
+            ..self
+        }
+    }
+
+    pub fn exposed_ports(&self) -> Value {
+        let mut ports: Value = json!({});
+
+        for binding in self.port_bindings.iter() {
+            ports[binding.container_port] = json!({});
+        }
+
+        ports
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut ports: Value = json!({});
+
+        for binding in self.port_bindings.iter() {
+            let entry: Value = json!({"HostIp": binding.host_ip.unwrap_or(""), "HostPort": binding.host_port});
+
+            match ports.get_mut(binding.container_port).and_then(Value::as_array_mut) {
+                Some(entries) => entries.push(entry),
+                None => ports[binding.container_port] = json!([entry]),
+            }
+        }
+
+        let mounts: Vec<Value> = self.mounts.iter().map(ContainerMount::to_json).collect();
+        let devices: Vec<Value> = self.devices.iter().map(ContainerDevice::to_json).collect();
+        let mut payload: Value = json!({
+            "Binds": self.binds,
+            "Mounts": mounts,
+            "Devices": devices,
+            "PortBindings": ports,
+            "AutoRemove": self.auto_remove,
+            "Privileged": self.privileged,
+        });
+
+        if let Some(value) = self.shm_size {
+            payload["ShmSize"] = json!(value);
+        }
+
+        if let Some(value) = self.network_mode {
+            payload["NetworkMode"] = json!(value);
+        }
+
+        if let Some(value) = &self.restart_policy {
+            payload["RestartPolicy"] = value.to_json();
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ContainerResources {
+    pub memory: Option<u64>,
+    pub memory_swap: Option<i64>,
+    pub cpu_quota: Option<i64>,
+    pub cpu_period: Option<u64>,
+    pub pids_limit: Option<i64>,
+}
+
+impl ContainerResources {
+    pub fn write_json(&self, payload: &mut Value) {
+        if let Some(value) = self.memory {
+            payload["Memory"] = json!(value);
+        }
+
+        if let Some(value) = self.memory_swap {
+            payload["MemorySwap"] = json!(value);
+        }
+
+        if let Some(value) = self.cpu_quota {
+            payload["CpuQuota"] = json!(value);
+        }
+
+        if let Some(value) = self.cpu_period {
+            payload["CpuPeriod"] = json!(value);
+        }
+
+        if let Some(value) = self.pids_limit {
+            payload["PidsLimit"] = json!(value);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
 pub struct ContainerCreateSpec<'a> {
     pub image: &'a str,
     pub command: Vec<&'a str>,
+    pub entrypoint: Option<Vec<&'a str>>,
+    pub env: Vec<&'a str>,
+    pub working_dir: Option<&'a str>,
+    pub user: Option<&'a str>,
+    pub hostname: Option<&'a str>,
+    pub stdin: bool,
+    pub platform: Option<&'a str>,
+    pub labels: HashMap<&'a str, &'a str>,
+    pub host_config: ContainerHostConfig<'a>,
+    pub resources: ContainerResources,
+}
+
+impl<'a> ContainerCreateSpec<'a> {
+    pub fn new(image: &'a str) -> Self {
+        Self {
+            image,
+            ..Self::default()
+        }
+    }
+
+    pub fn command(self, command: Vec<&'a str>) -> Self {
+        Self { command, ..self }
+    }
+
+    pub fn entrypoint(self, entrypoint: Vec<&'a str>) -> Self {
+        Self {
+            entrypoint: Some(entrypoint),
+            ..self
+        }
+    }
+
+    pub fn env(mut self, value: &'a str) -> Self {
+        self.env.push(value);
+        self
+    }
+
+    pub fn working_dir(self, value: &'a str) -> Self {
+        Self {
+            working_dir: Some(value),
+            ..self
+        }
+    }
+
+    pub fn user(self, value: &'a str) -> Self {
+        Self {
+            user: Some(value),
+            ..self
+        }
+    }
+
+    pub fn hostname(self, value: &'a str) -> Self {
+        Self {
+            hostname: Some(value),
+            ..self
+        }
+    }
+
+    pub fn stdin(self, value: bool) -> Self {
+        Self { stdin: value, ..self }
+    }
+
+    pub fn platform(self, value: &'a str) -> Self {
+        Self {
+            platform: Some(value),
+            ..self
+        }
+    }
+
+    pub fn label(mut self, key: &'a str, value: &'a str) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    pub fn host_config(self, value: ContainerHostConfig<'a>) -> Self {
+        Self {
+            host_config: value,
+            ..self
+        }
+    }
+
+    pub fn resources(self, value: ContainerResources) -> Self {
+        Self {
+            resources: value,
+            ..self
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut host_config: Value = self.host_config.to_json();
+        self.resources.write_json(&mut host_config);
+
+        let mut payload: Value = json!({
+            "Image": self.image,
+            "Cmd": self.command,
+            "Env": self.env,
+            "Labels": self.labels,
+            "AttachStdin": self.stdin,
+            "OpenStdin": self.stdin,
+            "StdinOnce": self.stdin,
+            "ExposedPorts": self.host_config.exposed_ports(),
+            "HostConfig": host_config,
+        });
+
+        if let Some(value) = &self.entrypoint {
+            payload["Entrypoint"] = json!(value);
+        }
+
+        if let Some(value) = self.working_dir {
+            payload["WorkingDir"] = json!(value);
+        }
+
+        if let Some(value) = self.user {
+            payload["User"] = json!(value);
+        }
+
+        if let Some(value) = self.hostname {
+            payload["Hostname"] = json!(value);
+        }
+
+        payload
+    }
+
+    pub(crate) fn to_query(&self) -> String {
+        match self.platform {
+            Some(value) => format!("platform={}", encode(value)),
+            None => String::new(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ContainerWaitResponseExitError {
     #[serde(rename = "Message")]
-    pub message: String,
+    pub message: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -69,6 +896,23 @@ pub struct ContainerWaitResponse {
     pub error: Option<ContainerWaitResponseExitError>,
 }
 
+#[derive(Debug, Clone, Copy)]
+pub enum ContainerWaitCondition {
+    NotRunning,
+    NextExit,
+    Removed,
+}
+
+impl ContainerWaitCondition {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContainerWaitCondition::NotRunning => "not-running",
+            ContainerWaitCondition::NextExit => "next-exit",
+            ContainerWaitCondition::Removed => "removed",
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ContainerWait {
     Succeeded(ContainerWaitResponse),
@@ -86,6 +930,100 @@ pub enum ContainerRemove {
     ServerError(ErrorResponse),
 }
 
+#[derive(Debug, Clone)]
+pub struct ContainerLogsOptions {
+    stdout: bool,
+    stderr: bool,
+    follow: bool,
+    timestamps: bool,
+    tail: Option<u64>,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl Default for ContainerLogsOptions {
+    fn default() -> Self {
+        Self {
+            stdout: true,
+            stderr: false,
+            follow: false,
+            timestamps: false,
+            tail: None,
+            since: None,
+            until: None,
+        }
+    }
+}
+
+impl ContainerLogsOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn stdout(self, value: bool) -> Self {
+        Self { stdout: value, ..self }
+    }
+
+    pub fn stderr(self, value: bool) -> Self {
+        Self { stderr: value, ..self }
+    }
+
+    pub fn follow(self, value: bool) -> Self {
+        Self { follow: value, ..self }
+    }
+
+    pub fn timestamps(self, value: bool) -> Self {
+        Self {
+            timestamps: value,
+            ..self
+        }
+    }
+
+    pub fn tail(self, lines: u64) -> Self {
+        Self {
+            tail: Some(lines),
+            ..self
+        }
+    }
+
+    pub fn since(self, timestamp: i64) -> Self {
+        Self {
+            since: Some(timestamp),
+            ..self
+        }
+    }
+
+    pub fn until(self, timestamp: i64) -> Self {
+        Self {
+            until: Some(timestamp),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_query(&self) -> String {
+        let mut query: Vec<String> = vec![
+            format!("stdout={}", self.stdout),
+            format!("stderr={}", self.stderr),
+            format!("follow={}", self.follow),
+            format!("timestamps={}", self.timestamps),
+        ];
+
+        if let Some(value) = self.tail {
+            query.push(format!("tail={value}"));
+        }
+
+        if let Some(value) = self.since {
+            query.push(format!("since={value}"));
+        }
+
+        if let Some(value) = self.until {
+            query.push(format!("until={value}"));
+        }
+
+        query.join("&")
+    }
+}
+
 #[derive(Debug)]
 pub enum ContainerLogs {
     Succeeded(ContainerLogsStream),
@@ -101,6 +1039,14 @@ pub enum ContainerAttach {
     ServerError(ErrorResponse),
 }
 
+#[derive(Debug)]
+pub enum ContainerAttachStdin {
+    Succeeded(ContainerStdin, ContainerAttachStream),
+    BadParameter(ErrorResponse),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
 #[derive(Debug)]
 pub enum ContainerUpload {
     Succeeded,
@@ -110,6 +1056,50 @@ pub enum ContainerUpload {
     ServerError(ErrorResponse),
 }
 
+#[derive(Debug)]
+pub enum ContainerDownload {
+    Succeeded(ContainerDownloadStream),
+    BadParameter(ErrorResponse),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerCommitResponse {
+    #[serde(rename = "Id")]
+    pub id: String,
+}
+
+#[derive(Debug)]
+pub enum ContainerCommit {
+    Succeeded(ContainerCommitResponse),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerExport {
+    Succeeded(ContainerDownloadStream),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerArchive {
+    Succeeded(ContainerArchiveStream),
+    NoSuchContainer(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerReadFile {
+    Succeeded(ContainerArchiveStream),
+    NoSuchContainer(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ErrorResponse {
     pub message: String,
@@ -130,3 +1120,421 @@ pub enum ContainerStop {
     NoSuchContainer(ErrorResponse),
     ServerError(ErrorResponse),
 }
+
+#[derive(Debug, Default)]
+pub struct ContainerPruneFilters<'a> {
+    labels: Vec<&'a str>,
+    until: Option<&'a str>,
+}
+
+impl<'a> ContainerPruneFilters<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn label(mut self, value: &'a str) -> Self {
+        self.labels.push(value);
+        self
+    }
+
+    pub fn until(self, value: &'a str) -> Self {
+        Self {
+            until: Some(value),
+            ..self
+        }
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut payload: Value = json!({"label": self.labels});
+
+        if let Some(until) = self.until {
+            payload["until"] = json!([until]);
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerPruneResponse {
+    #[serde(rename = "ContainersDeleted")]
+    pub containers_deleted: Option<Vec<String>>,
+    #[serde(rename = "SpaceReclaimed")]
+    pub space_reclaimed: u64,
+}
+
+#[derive(Debug)]
+pub enum ContainerPrune {
+    Succeeded(ContainerPruneResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerTopResponse {
+    #[serde(rename = "Titles")]
+    pub titles: Vec<String>,
+    #[serde(rename = "Processes")]
+    pub processes: Vec<Vec<String>>,
+}
+
+impl ContainerTopResponse {
+    pub fn rows(&self) -> Vec<HashMap<&str, &str>> {
+        let mut rows: Vec<HashMap<&str, &str>> = Vec::with_capacity(self.processes.len());
+
+        for process in self.processes.iter() {
+            let row = self.titles.iter().zip(process.iter());
+            rows.push(row.map(|(title, value)| (title.as_str(), value.as_str())).collect());
+        }
+
+        rows
+    }
+}
+
+#[derive(Debug)]
+pub enum ContainerTop {
+    Succeeded(ContainerTopResponse),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Clone)]
+pub enum ContainerRestartPolicy {
+    No,
+    Always,
+    UnlessStopped,
+    OnFailure(u32),
+}
+
+impl ContainerRestartPolicy {
+    pub fn to_json(&self) -> Value {
+        match self {
+            ContainerRestartPolicy::No => json!({"Name": "no"}),
+            ContainerRestartPolicy::Always => json!({"Name": "always"}),
+            ContainerRestartPolicy::UnlessStopped => json!({"Name": "unless-stopped"}),
+            ContainerRestartPolicy::OnFailure(retries) => json!({"Name": "on-failure", "MaximumRetryCount": retries}),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ContainerUpdateSpec {
+    pub cpu_shares: Option<u64>,
+    pub memory: Option<u64>,
+    pub memory_swap: Option<i64>,
+    pub restart_policy: Option<ContainerRestartPolicy>,
+}
+
+impl ContainerUpdateSpec {
+    pub fn to_json(&self) -> Value {
+        let mut payload: Value = json!({});
+
+        if let Some(value) = self.cpu_shares {
+            payload["CpuShares"] = json!(value);
+        }
+
+        if let Some(value) = self.memory {
+            payload["Memory"] = json!(value);
+        }
+
+        if let Some(value) = self.memory_swap {
+            payload["MemorySwap"] = json!(value);
+        }
+
+        if let Some(value) = &self.restart_policy {
+            payload["RestartPolicy"] = value.to_json();
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerUpdateResponse {
+    #[serde(rename = "Warnings")]
+    pub warnings: Option<Vec<String>>,
+}
+
+#[derive(Debug)]
+pub enum ContainerUpdate {
+    Succeeded(ContainerUpdateResponse),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerRename {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerPause {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerUnpause {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerResize {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerRestart {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ContainerKill {
+    Succeeded,
+    NoSuchContainer(ErrorResponse),
+    NotRunning(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Default)]
+pub struct ExecCreateSpec<'a> {
+    pub command: Vec<&'a str>,
+    pub env: Vec<&'a str>,
+    pub user: Option<&'a str>,
+    pub working_dir: Option<&'a str>,
+    pub privileged: bool,
+}
+
+impl ExecCreateSpec<'_> {
+    pub fn to_json(&self) -> Value {
+        let mut payload: Value = json!({
+            "AttachStdout": true,
+            "AttachStderr": true,
+            "Cmd": self.command,
+            "Env": self.env,
+            "Privileged": self.privileged,
+        });
+
+        if let Some(value) = self.user {
+            payload["User"] = json!(value);
+        }
+
+        if let Some(value) = self.working_dir {
+            payload["WorkingDir"] = json!(value);
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecCreateResponse {
+    #[serde(rename = "Id")]
+    pub id: String,
+}
+
+#[derive(Debug)]
+pub enum ExecCreate {
+    Succeeded(ExecCreateResponse),
+    NoSuchContainer(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ExecStart {
+    Succeeded,
+    NoSuchInstance(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ExecAttach {
+    Succeeded(ContainerLogsStream),
+    NoSuchInstance(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecProcessConfig {
+    pub entrypoint: String,
+    pub arguments: Vec<String>,
+    pub privileged: Option<bool>,
+    pub user: Option<String>,
+    pub tty: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExecInspectResponse {
+    #[serde(rename = "ID")]
+    pub id: String,
+    #[serde(rename = "ContainerID")]
+    pub container_id: String,
+    #[serde(rename = "Running")]
+    pub running: bool,
+    #[serde(rename = "ExitCode")]
+    pub exit_code: Option<i64>,
+    #[serde(rename = "Pid")]
+    pub pid: i64,
+    #[serde(rename = "OpenStdin")]
+    pub open_stdin: bool,
+    #[serde(rename = "OpenStdout")]
+    pub open_stdout: bool,
+    #[serde(rename = "OpenStderr")]
+    pub open_stderr: bool,
+    #[serde(rename = "ProcessConfig")]
+    pub process_config: ExecProcessConfig,
+}
+
+#[derive(Debug)]
+pub enum ExecInspect {
+    Succeeded(ExecInspectResponse),
+    NoSuchInstance(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum ExecResize {
+    Succeeded,
+    BadParameter(ErrorResponse),
+    NoSuchInstance(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectHealth {
+    #[serde(rename = "Status")]
+    pub status: String,
+    #[serde(rename = "FailingStreak")]
+    pub failing_streak: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectState {
+    #[serde(rename = "Status")]
+    pub status: String,
+    #[serde(rename = "Running")]
+    pub running: bool,
+    #[serde(rename = "Paused")]
+    pub paused: bool,
+    #[serde(rename = "Restarting")]
+    pub restarting: bool,
+    #[serde(rename = "OOMKilled")]
+    pub oom_killed: bool,
+    #[serde(rename = "Dead")]
+    pub dead: bool,
+    #[serde(rename = "Pid")]
+    pub pid: i64,
+    #[serde(rename = "ExitCode")]
+    pub exit_code: i64,
+    #[serde(rename = "Error")]
+    pub error: String,
+    #[serde(rename = "StartedAt")]
+    pub started_at: String,
+    #[serde(rename = "FinishedAt")]
+    pub finished_at: String,
+    #[serde(rename = "Health")]
+    pub health: Option<ContainerInspectHealth>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectMount {
+    #[serde(rename = "Type")]
+    pub kind: String,
+    #[serde(rename = "Name")]
+    pub name: Option<String>,
+    #[serde(rename = "Source")]
+    pub source: String,
+    #[serde(rename = "Destination")]
+    pub destination: String,
+    #[serde(rename = "Mode")]
+    pub mode: String,
+    #[serde(rename = "RW")]
+    pub rw: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectConfig {
+    #[serde(rename = "Hostname")]
+    pub hostname: String,
+    #[serde(rename = "User")]
+    pub user: String,
+    #[serde(rename = "Env")]
+    pub env: Option<Vec<String>>,
+    #[serde(rename = "Cmd")]
+    pub command: Option<Vec<String>>,
+    #[serde(rename = "Entrypoint")]
+    pub entrypoint: Option<Vec<String>>,
+    #[serde(rename = "Image")]
+    pub image: String,
+    #[serde(rename = "WorkingDir")]
+    pub working_dir: String,
+    #[serde(rename = "Labels")]
+    pub labels: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectNetwork {
+    #[serde(rename = "NetworkID")]
+    pub network_id: String,
+    #[serde(rename = "IPAddress")]
+    pub ip_address: String,
+    #[serde(rename = "Gateway")]
+    pub gateway: String,
+    #[serde(rename = "MacAddress")]
+    pub mac_address: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectNetworkSettings {
+    #[serde(rename = "IPAddress")]
+    pub ip_address: String,
+    #[serde(rename = "Gateway")]
+    pub gateway: String,
+    #[serde(rename = "Networks")]
+    pub networks: Option<HashMap<String, ContainerInspectNetwork>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ContainerInspectResponse {
+    #[serde(rename = "Id")]
+    pub id: String,
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Created")]
+    pub created: String,
+    #[serde(rename = "Path")]
+    pub path: String,
+    #[serde(rename = "Args")]
+    pub args: Vec<String>,
+    #[serde(rename = "Image")]
+    pub image: String,
+    #[serde(rename = "RestartCount")]
+    pub restart_count: i64,
+    #[serde(rename = "State")]
+    pub state: ContainerInspectState,
+    #[serde(rename = "Mounts")]
+    pub mounts: Vec<ContainerInspectMount>,
+    #[serde(rename = "Config")]
+    pub config: ContainerInspectConfig,
+    #[serde(rename = "NetworkSettings")]
+    pub network_settings: ContainerInspectNetworkSettings,
+}
+
+#[derive(Debug)]
+pub enum ContainerInspect {
+    Succeeded(Box<ContainerInspectResponse>),
+    NoSuchContainer(ErrorResponse),
+    ServerError(ErrorResponse),
+}
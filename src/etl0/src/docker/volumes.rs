@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use http_body_util::Full;
+use hyper::body::Bytes;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::client::DockerClient;
+use super::error::{DockerError, DockerResult};
+use super::http::DockerConnection;
+use super::query::{encode, encode_json};
+use super::types::ErrorResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct VolumeInfo {
+    #[serde(rename = "Name")]
+    pub name: String,
+    #[serde(rename = "Driver")]
+    pub driver: String,
+    #[serde(rename = "Mountpoint")]
+    pub mountpoint: String,
+    #[serde(rename = "CreatedAt")]
+    pub created_at: Option<String>,
+    #[serde(rename = "Scope")]
+    pub scope: String,
+    #[serde(rename = "Labels")]
+    pub labels: Option<HashMap<String, String>>,
+    #[serde(rename = "Options")]
+    pub options: Option<HashMap<String, String>>,
+    #[serde(rename = "UsageData")]
+    pub usage_data: Option<VolumeUsageData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VolumeUsageData {
+    #[serde(rename = "Size")]
+    pub size: i64,
+    #[serde(rename = "RefCount")]
+    pub ref_count: i64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VolumeCreateSpec<'a> {
+    pub name: Option<&'a str>,
+    pub driver: Option<&'a str>,
+    pub driver_opts: HashMap<&'a str, &'a str>,
+    pub labels: HashMap<&'a str, &'a str>,
+}
+
+impl<'a> VolumeCreateSpec<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn name(self, value: &'a str) -> Self {
+        Self {
+            name: Some(value),
+            ..self
+        }
+    }
+
+    pub fn driver(self, value: &'a str) -> Self {
+        Self {
+            driver: Some(value),
+            ..self
+        }
+    }
+
+    pub fn driver_opt(mut self, key: &'a str, value: &'a str) -> Self {
+        self.driver_opts.insert(key, value);
+        self
+    }
+
+    pub fn label(mut self, key: &'a str, value: &'a str) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut payload: Value = json!({
+            "DriverOpts": self.driver_opts,
+            "Labels": self.labels,
+        });
+
+        if let Some(value) = self.name {
+            payload["Name"] = json!(value);
+        }
+
+        if let Some(value) = self.driver {
+            payload["Driver"] = json!(value);
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct VolumeFilters<'a> {
+    dangling: Option<bool>,
+    labels: Vec<&'a str>,
+    names: Vec<&'a str>,
+}
+
+impl<'a> VolumeFilters<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn dangling(self, value: bool) -> Self {
+        Self {
+            dangling: Some(value),
+            ..self
+        }
+    }
+
+    pub fn label(mut self, value: &'a str) -> Self {
+        self.labels.push(value);
+        self
+    }
+
+    pub fn name(mut self, value: &'a str) -> Self {
+        self.names.push(value);
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut payload: Value = json!({"label": self.labels, "name": self.names});
+
+        if let Some(dangling) = self.dangling {
+            payload["dangling"] = json!([dangling.to_string()]);
+        }
+
+        payload
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VolumeListResponse {
+    #[serde(rename = "Volumes")]
+    pub volumes: Option<Vec<VolumeInfo>>,
+    #[serde(rename = "Warnings")]
+    pub warnings: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct VolumePruneResponse {
+    #[serde(rename = "VolumesDeleted")]
+    pub volumes_deleted: Option<Vec<String>>,
+    #[serde(rename = "SpaceReclaimed")]
+    pub space_reclaimed: u64,
+}
+
+#[derive(Debug)]
+pub enum VolumeCreate {
+    Succeeded(Box<VolumeInfo>),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum VolumeList {
+    Succeeded(VolumeListResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum VolumeInspect {
+    Succeeded(Box<VolumeInfo>),
+    NoSuchVolume(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum VolumeRemove {
+    Succeeded,
+    NoSuchVolume(ErrorResponse),
+    Conflict(ErrorResponse),
+    ServerError(ErrorResponse),
+}
+
+#[derive(Debug)]
+pub enum VolumePrune {
+    Succeeded(VolumePruneResponse),
+    ServerError(ErrorResponse),
+}
+
+impl DockerClient {
+    pub async fn volumes_create(&self, spec: &VolumeCreateSpec<'_>) -> DockerResult<VolumeCreate> {
+        let url: String = format!("{}/volumes/create", self.prefix);
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, Some(self.stamp(spec.to_json()))).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(VolumeCreate::Succeeded(Box::new(value))),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(VolumeCreate::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn volumes_list(&self, filters: &VolumeFilters<'_>) -> DockerResult<VolumeList> {
+        let url: String = format!("{}/volumes?filters={}", self.prefix, encode_json(&filters.to_json()));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(VolumeList::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(VolumeList::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn volumes_inspect(&self, name: &str) -> DockerResult<VolumeInspect> {
+        let url: String = format!("{}/volumes/{}", self.prefix, encode(name));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.get(&url).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(VolumeInspect::Succeeded(Box::new(value))),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(VolumeInspect::NoSuchVolume(response.into_error().await?)),
+                    500 => Ok(VolumeInspect::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn volumes_remove(&self, name: &str, force: bool) -> DockerResult<VolumeRemove> {
+        let url: String = format!("{}/volumes/{}?force={force}", self.prefix, encode(name));
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.delete(&url).await {
+            Ok(response) => match response.into_bytes().await {
+                Ok(_) => Ok(VolumeRemove::Succeeded),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    404 => Ok(VolumeRemove::NoSuchVolume(response.into_error().await?)),
+                    409 => Ok(VolumeRemove::Conflict(response.into_error().await?)),
+                    500 => Ok(VolumeRemove::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn volumes_prune(&self, filters: &VolumeFilters<'_>) -> DockerResult<VolumePrune> {
+        let url: String = format!(
+            "{}/volumes/prune?filters={}",
+            self.prefix,
+            encode_json(&filters.to_json())
+        );
+        let connection: DockerConnection<Full<Bytes>> = self.connect().await?;
+
+        match connection.post(&url, None).await {
+            Ok(response) => match response.into_json().await {
+                Ok(value) => Ok(VolumePrune::Succeeded(value)),
+                Err(error) => Err(error),
+            },
+            Err(error) => match error {
+                DockerError::StatusFailed(url, status, response) => match status.as_u16() {
+                    500 => Ok(VolumePrune::ServerError(response.into_error().await?)),
+                    _ => Err(DockerError::StatusFailed(url, status, response)),
+                },
+                error => Err(error),
+            },
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod docker;
+pub mod pipeline;
+pub mod prelude;
+pub mod tar;
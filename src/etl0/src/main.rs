@@ -1,17 +1,12 @@
-mod docker;
-mod pipeline;
-mod tar;
-
 use std::io::Write;
 use chrono::Utc;
 
-use tar::TarChunk;
 use tokio;
 use tokio_stream::StreamExt;
 
-use crate::docker::{ContainerAttach, ContainerCreateSpec, ContainerList};
-use crate::docker::{ContainerCreate, ContainerCreateResponse, DockerClient, ImageCreate};
-use crate::tar::TarArchive;
+use etl0::docker::{ContainerAttach, ContainerCreateSpec, ContainerList, ContainerListOptions, ContainerWaitCondition};
+use etl0::docker::{ContainerCreate, ContainerCreateResponse, ImageCreate, ImageReference};
+use etl0::prelude::*;
 
 async fn archive_test() {
     let mut archive = TarArchive::new();
@@ -34,14 +29,11 @@ async fn archive_test() {
     }
 }
 
-#[tokio::main]
-async fn main() {
-    return archive_test().await;
-
+async fn docker_test() {
     let socket = "/var/run/docker.sock";
     let engine: DockerClient = DockerClient::open(socket);
 
-    match engine.images_create().await {
+    match engine.images_create(&ImageReference::parse("ubuntu:latest"), None).await {
         Err(error) => return println!("{:?}", error),
         Ok(value) => match value {
             ImageCreate::Succeeded(mut stream) => {
@@ -53,15 +45,12 @@ async fn main() {
         },
     }
 
-    let spec = ContainerCreateSpec {
-        image: "ubuntu:latest",
-        command: vec![
-            "sha256sum",
-            "/opt/lubuntu-22.04.3-desktop-amd64.iso",
-            "/opt/enwiki-20230801-pages-meta-history27.xml-p74198591p74500204",
-            "/opt/qemu-8.2.1.tar.xz",
-        ],
-    };
+    let spec = ContainerCreateSpec::new("ubuntu:latest").command(vec![
+        "sha256sum",
+        "/opt/lubuntu-22.04.3-desktop-amd64.iso",
+        "/opt/enwiki-20230801-pages-meta-history27.xml-p74198591p74500204",
+        "/opt/qemu-8.2.1.tar.xz",
+    ]);
 
     let container: ContainerCreateResponse = match engine.containers_create(&spec).await {
         Err(error) => return println!("{:?}", error),
@@ -87,10 +76,10 @@ async fn main() {
         println!("{} {:?}", Utc::now().timestamp_millis(), item);
     }
 
-    println!("{:?}", engine.containers_wait(&container.id).await);
+    println!("{:?}", engine.containers_wait(&container.id, ContainerWaitCondition::NotRunning).await);
     println!("{:?}", engine.containers_stop(&container.id).await);
 
-    match engine.containers_list().await {
+    match engine.containers_list(&ContainerListOptions::new().all(true)).await {
         Err(error) => println!("{}", error),
         Ok(ContainerList::BadParameter(value)) => println!("{:?}", value),
         Ok(ContainerList::ServerError(value)) => println!("{:?}", value),
@@ -104,9 +93,17 @@ async fn main() {
                 //);
 
                 if container.image == spec.image {
-                    println!("{:?}", engine.containers_remove(&container.id).await);
+                    println!("{:?}", engine.containers_remove(&container.id, false).await);
                 }
             }
         }
     }
 }
+
+#[tokio::main]
+async fn main() {
+    match std::env::args().nth(1).as_deref() {
+        Some("docker") => docker_test().await,
+        _ => archive_test().await,
+    }
+}
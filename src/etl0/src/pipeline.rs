@@ -1,12 +1,85 @@
+use std::collections::HashMap;
 use std::fs::{read_dir, DirEntry, Metadata, ReadDir};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::slice::Iter;
 use std::str::Lines;
+use std::time::Duration;
 
-use regex::Regex;
+use regex::{Captures, Regex};
+use thiserror::Error;
 use tokio::fs::File;
 use tokio::io::AsyncReadExt;
 
+use crate::tar::TarArchive;
+
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("[E101] Cannot read pipeline '{0}'")]
+    IOFailed(String, #[source] std::io::Error),
+
+    #[error("[E102] Cannot use pipeline path '{0}', because it is not valid utf8")]
+    InvalidPath(String),
+
+    #[error("[E103] Cannot find plugin of task in '{0}' at line {1}")]
+    MissingPlugin(String, usize),
+
+    #[error("[E104] Cannot recognize plugin '{2}' in '{0}' at line {1}")]
+    InvalidPlugin(String, usize, String),
+
+    #[error("[E105] Cannot accept file name '{2}' in '{0}' at line {1}, it escapes the task directory")]
+    InvalidFileName(String, usize, String),
+
+    #[error("[E106] Cannot name task '{1}' in '{0}' at line {3}, it is already used at line {2}")]
+    DuplicateTask(String, String, usize, usize),
+
+    #[error("[E107] Cannot accept task name '{2}' in '{0}' at line {1}, only letters, digits, '-' and '_' are allowed")]
+    InvalidTaskName(String, usize, String),
+}
+
+pub type PipelineResult<T> = Result<T, PipelineError>;
+
+impl PipelineError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            PipelineError::IOFailed(..) => "E101",
+            PipelineError::InvalidPath(..) => "E102",
+            PipelineError::MissingPlugin(..) => "E103",
+            PipelineError::InvalidPlugin(..) => "E104",
+            PipelineError::InvalidFileName(..) => "E105",
+            PipelineError::DuplicateTask(..) => "E106",
+            PipelineError::InvalidTaskName(..) => "E107",
+        }
+    }
+
+    fn raise_io_failed<T>(path: impl AsRef<Path>, error: std::io::Error) -> PipelineResult<T> {
+        Err(Self::IOFailed(path.as_ref().to_string_lossy().to_string(), error))
+    }
+
+    fn raise_invalid_path<T>(path: impl AsRef<Path>) -> PipelineResult<T> {
+        Err(Self::InvalidPath(path.as_ref().to_string_lossy().to_string()))
+    }
+
+    fn raise_missing_plugin<T>(path: &str, line: usize) -> PipelineResult<T> {
+        Err(Self::MissingPlugin(path.to_owned(), line + 1))
+    }
+
+    fn raise_invalid_plugin<T>(path: &str, line: usize, value: &str) -> PipelineResult<T> {
+        Err(Self::InvalidPlugin(path.to_owned(), line + 1, value.to_owned()))
+    }
+
+    fn raise_invalid_file_name<T>(path: &str, line: usize, name: &str) -> PipelineResult<T> {
+        Err(Self::InvalidFileName(path.to_owned(), line + 1, name.to_owned()))
+    }
+
+    fn raise_duplicate_task<T>(path: &str, name: &str, first: usize, second: usize) -> PipelineResult<T> {
+        Err(Self::DuplicateTask(path.to_owned(), name.to_owned(), first + 1, second + 1))
+    }
+
+    fn raise_invalid_task_name<T>(path: &str, line: usize, name: &str) -> PipelineResult<T> {
+        Err(Self::InvalidTaskName(path.to_owned(), line + 1, name.to_owned()))
+    }
+}
+
 #[derive(Debug)]
 pub struct Semver {
     pub major: u16,
@@ -28,29 +101,39 @@ pub struct Pipeline {
 }
 
 impl Pipeline {
-    async fn open(path: PathBuf) -> Self {
+    async fn open(root: &Path, path: PathBuf) -> PipelineResult<Self> {
         let mut file: File = match File::open(&path).await {
-            Err(error) => panic!("{:?}", error),
+            Err(error) => return PipelineError::raise_io_failed(&path, error),
             Ok(value) => value,
         };
 
         let mut content: String = String::with_capacity(10 * 1024);
         let length: usize = match file.read_to_string(&mut content).await {
-            Err(error) => panic!("{:?}", error),
+            Err(error) => return PipelineError::raise_io_failed(&path, error),
             Ok(value) => value,
         };
 
         let lines: Lines = content.lines();
         let path = match path.to_str() {
-            None => panic!("{:?}", "path"),
+            None => return PipelineError::raise_invalid_path(&path),
             Some(value) => value.to_owned(),
         };
 
-        Self {
+        // ids hash the path below the pipelines root, so they do not
+        // change with the directory the pipelines were found from
+        let scope: String = match Path::new(&path).strip_prefix(root) {
+            Ok(value) => value.to_string_lossy().into_owned(),
+            Err(_) => path.clone(),
+        };
+
+        let mut tasks: Vec<Task> = Task::read_all(&path, lines)?;
+        Task::identify_all(&path, &scope, &mut tasks)?;
+
+        Ok(Self {
             path: path,
             length: length,
-            tasks: Task::read_all(lines),
-        }
+            tasks: tasks,
+        })
     }
 
     pub fn tasks(&self) -> Iter<'_, Task> {
@@ -60,54 +143,288 @@ impl Pipeline {
 
 #[derive(Debug)]
 pub struct Task {
+    pub id: String,
+    pub name: String,
     pub line: usize,
     pub content: String,
     pub image: String,
     pub plugin: PluginRef,
+    pub meta: TaskMeta,
+    pub files: Vec<TaskFile>,
+}
+
+#[derive(Debug, Default)]
+pub struct TaskMeta {
+    pub name: Option<String>,
+    pub image: Option<String>,
+    pub needs: Vec<String>,
+    pub timeout: Option<Duration>,
+    pub resources: TaskResources,
+    pub env: Vec<(String, String)>,
+    pub warnings: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct TaskResources {
+    pub cpus: Option<f64>,
+    pub memory: Option<u64>,
+}
+
+impl TaskMeta {
+    // the first meta line names the plugin, every following one
+    // is expected to look like '``` key: value'
+    fn parse(line: usize, meta: &[&str]) -> Self {
+        let mut result: Self = Self::default();
+
+        for (offset, value) in meta.iter().enumerate().skip(1) {
+            let position: usize = line + offset + 1;
+            let entry: &str = value.trim_start_matches('`').trim();
+
+            let (key, value) = match entry.split_once(':') {
+                None => {
+                    result.warn(position, format!("expected 'key: value', found '{entry}'"));
+                    continue;
+                }
+                Some((key, value)) => (key.trim(), value.trim()),
+            };
+
+            match key {
+                // explicit names are validated together with derived ones
+                "name" => result.name = Some(value.to_owned()),
+                "image" => match value.is_empty() || value.contains(char::is_whitespace) {
+                    true => result.warn(position, format!("invalid image '{value}'")),
+                    false => result.image = Some(value.to_owned()),
+                },
+                "needs" => result.parse_needs(value),
+                "timeout" => match Self::parse_duration(value) {
+                    None => result.warn(position, format!("invalid timeout '{value}'")),
+                    Some(duration) => result.timeout = Some(duration),
+                },
+                "resources" => result.parse_resources(position, value),
+                "env" => match value.split_once('=') {
+                    None => result.warn(position, format!("expected 'env: NAME=value', found '{value}'")),
+                    Some((name, value)) => result.env.push((name.trim().to_owned(), value.trim().to_owned())),
+                },
+                key => result.warn(position, format!("unknown key '{key}'")),
+            }
+        }
+
+        result
+    }
+
+    fn warn(&mut self, line: usize, message: String) {
+        self.warnings.push(format!("line {line}: {message}"));
+    }
+
+    fn is_name(value: &str) -> bool {
+        !value.is_empty() && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    fn parse_needs(&mut self, value: &str) {
+        for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            self.needs.push(name.to_owned());
+        }
+    }
+
+    fn parse_resources(&mut self, line: usize, value: &str) {
+        for item in value.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            match item.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+                Some(("cpus", value)) => match value.parse::<f64>() {
+                    Ok(cpus) if cpus > 0.0 => self.resources.cpus = Some(cpus),
+                    _ => self.warn(line, format!("invalid cpus '{value}'")),
+                },
+                Some(("memory", value)) => match Self::parse_bytes(value) {
+                    None => self.warn(line, format!("invalid memory '{value}'")),
+                    Some(memory) => self.resources.memory = Some(memory),
+                },
+                Some((key, _)) => self.warn(line, format!("unknown resource '{key}'")),
+                None => self.warn(line, format!("expected 'name=value', found '{item}'")),
+            }
+        }
+    }
+
+    fn parse_duration(value: &str) -> Option<Duration> {
+        let (number, unit) = value.split_at(value.trim_end_matches(char::is_alphabetic).len());
+        let number: u64 = number.trim().parse().ok()?;
+
+        match unit {
+            "" | "s" => Some(Duration::from_secs(number)),
+            "m" => number.checked_mul(60).map(Duration::from_secs),
+            "h" => number.checked_mul(3600).map(Duration::from_secs),
+            _ => None,
+        }
+    }
+
+    fn parse_bytes(value: &str) -> Option<u64> {
+        let (number, unit) = value.split_at(value.trim_end_matches(char::is_alphabetic).len());
+        let number: u64 = number.trim().parse().ok()?;
+
+        match unit.to_ascii_lowercase().as_str() {
+            "" | "b" => Some(number),
+            "k" => number.checked_mul(1 << 10),
+            "m" => number.checked_mul(1 << 20),
+            "g" => number.checked_mul(1 << 30),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct TaskFile {
+    pub name: String,
+    pub content: String,
+}
+
+impl TaskFile {
+    fn new(name: &str, content: &[&str]) -> Self {
+        Self {
+            name: name.trim().to_owned(),
+            content: content.join("\n"),
+        }
+    }
+
+    // the name lands in the task archive as is, so it has to stay
+    // relative and cannot climb out of the directory it is unpacked to
+    fn is_contained(name: &str) -> bool {
+        let name: &str = name.trim();
+        let mut components = Path::new(name).components();
+
+        !name.is_empty() && components.all(|value| matches!(value, Component::Normal(_) | Component::CurDir))
+    }
 }
 
 impl Task {
-    fn read_all(lines: Lines) -> Vec<Self> {
+    fn read_all(path: &str, lines: Lines) -> PipelineResult<Vec<Self>> {
         let mut start = 0;
         let mut tasks: Vec<Self> = Vec::new();
         let mut meta = Vec::new();
         let mut content = Vec::new();
+        let mut files: Vec<TaskFile> = Vec::new();
+        let mut file: Option<(&str, Vec<&str>)> = None;
 
         for (index, line) in lines.enumerate() {
-            if line.starts_with("``` ") {
-                if content.len() > 0 {
-                    tasks.push(Self::read(start, &meta, &content));
+            if let Some(name) = line.strip_prefix("``` file: ") {
+                if !TaskFile::is_contained(name) {
+                    return PipelineError::raise_invalid_file_name(path, index, name.trim());
+                }
+
+                if let Some((name, lines)) = file.take() {
+                    files.push(TaskFile::new(name, &lines));
+                }
+
+                file = Some((name, Vec::new()));
+            } else if line.starts_with("``` ") {
+                if let Some((name, lines)) = file.take() {
+                    files.push(TaskFile::new(name, &lines));
+                }
+
+                if !content.is_empty() || !files.is_empty() {
+                    tasks.push(Self::read(path, start, &meta, &content, files)?);
                     meta.clear();
                     content.clear();
+                    files = Vec::new();
                 }
 
-                if content.len() == 0 {
+                if meta.is_empty() {
                     start = index;
                 }
 
                 meta.push(line);
+            } else if let Some((_, lines)) = &mut file {
+                lines.push(line);
             } else {
                 content.push(line);
             }
         }
 
-        if content.len() > 0 {
-            tasks.push(Self::read(start, &meta, &content));
+        if let Some((name, lines)) = file.take() {
+            files.push(TaskFile::new(name, &lines));
         }
 
-        tasks
+        if !content.is_empty() || !files.is_empty() {
+            tasks.push(Self::read(path, start, &meta, &content, files)?);
+        }
+
+        Ok(tasks)
     }
 
-    fn read(line: usize, meta: &[&str], content: &[&str]) -> Self {
-        Self {
+    fn read(path: &str, line: usize, meta: &[&str], content: &[&str], files: Vec<TaskFile>) -> PipelineResult<Self> {
+        let plugin: PluginRef = Self::extract_plugin(path, line, meta)?;
+        let meta: TaskMeta = TaskMeta::parse(line, meta);
+
+        Ok(Self {
+            id: "".to_owned(),
+            name: "".to_owned(),
             line: line,
             content: content.join("\n"),
-            image: "".to_owned(),
-            plugin: Self::extract_plugin(meta),
+            image: meta.image.clone().unwrap_or_default(),
+            plugin: plugin,
+            meta: meta,
+            files: files,
+        })
+    }
+
+    // tasks without an explicit name are named after their plugin,
+    // numbered when the same plugin appears more than once
+    fn identify_all(path: &str, scope: &str, tasks: &mut [Task]) -> PipelineResult<()> {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        let mut names: HashMap<String, usize> = HashMap::new();
+
+        for task in tasks.iter_mut() {
+            let name: String = match &task.meta.name {
+                Some(value) => value.clone(),
+                None => {
+                    let count: &mut usize = seen.entry(task.plugin.dep.clone()).or_default();
+                    *count += 1;
+
+                    match count {
+                        1 => task.plugin.dep.to_lowercase(),
+                        count => format!("{}-{count}", task.plugin.dep.to_lowercase()),
+                    }
+                }
+            };
+
+            if !TaskMeta::is_name(&name) {
+                return PipelineError::raise_invalid_task_name(path, task.line, &name);
+            }
+
+            // generated names may collide with explicit ones as well
+            if let Some(line) = names.insert(name.clone(), task.line) {
+                return PipelineError::raise_duplicate_task(path, &name, line, task.line);
+            }
+
+            task.id = Self::identity(scope, &name);
+            task.name = name;
+        }
+
+        Ok(())
+    }
+
+    // FNV-1a keeps ids stable across builds and toolchains,
+    // unlike the randomly seeded hasher from the standard library
+    fn identity(scope: &str, name: &str) -> String {
+        let mut hash: u64 = 0xcbf29ce484222325;
+
+        for byte in scope.bytes().chain([0]).chain(name.bytes()) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
         }
+
+        format!("{hash:016x}")
     }
 
-    fn extract_plugin(meta: &[&str]) -> PluginRef {
+    pub fn archive(&self, script: &str) -> TarArchive {
+        let mut archive: TarArchive = TarArchive::new();
+        archive.append_data(script.to_owned(), self.content.as_bytes().to_vec());
+
+        for file in self.files.iter() {
+            archive.append_data(file.name.clone(), file.content.as_bytes().to_vec());
+        }
+
+        archive
+    }
+
+    fn extract_plugin(path: &str, line: usize, meta: &[&str]) -> PipelineResult<PluginRef> {
         let vendor: &str = r"(?P<vendor>[a-zA-Z0-9]+)";
         let dep: &str = r"(?P<dep>[a-zA-Z0-9]+)";
         let semver: &str = r"((?P<major>\d+)\.(?P<minor>\d+)\.(?P<patch>\d+))";
@@ -118,49 +435,28 @@ impl Task {
             Ok(value) => value,
         };
 
-        match meta.get(0) {
-            None => todo!("missing index"),
-            Some(value) => match regex.captures(value) {
-                None => todo!("captures"),
-                Some(captures) => {
-                    let vendor: String = match captures.name("vendor") {
-                        None => todo!("vendor"),
-                        Some(value) => value.as_str().to_owned(),
-                    };
-
-                    let dep: String = match captures.name("dep") {
-                        None => todo!("dep"),
-                        Some(value) => value.as_str().to_owned(),
-                    };
-
-                    let major: u16 = match captures.name("major") {
-                        None => todo!("major"),
-                        Some(value) => match value.as_str().parse() {
-                            Err(_) => todo!("major"),
-                            Ok(value) => value,
-                        }
-                    };
-
-                    let minor: u16 = match captures.name("minor") {
-                        None => todo!("minor"),
-                        Some(value) => match value.as_str().parse() {
-                            Err(_) => todo!("minor"),
-                            Ok(value) => value,
-                        }
-                    };
-
-                    let patch: u16 = match captures.name("patch") {
-                        None => todo!("patch"),
-                        Some(value) => match value.as_str().parse() {
-                            Err(_) => todo!("patch"),
-                            Ok(value) => value,
-                        }
-                    };
-
-                    PluginRef::new(vendor, dep, Semver::new(major, minor, patch))
-                }
-            }
+        let value: &str = match meta.first() {
+            None => return PipelineError::raise_missing_plugin(path, line),
+            Some(value) => value,
+        };
+
+        let captures: Captures = match regex.captures(value) {
+            None => return PipelineError::raise_invalid_plugin(path, line, value),
+            Some(captures) => captures,
+        };
+
+        let mut version: [u16; 3] = [0; 3];
+        for (index, name) in ["major", "minor", "patch"].iter().enumerate() {
+            version[index] = match captures[*name].parse() {
+                Err(_) => return PipelineError::raise_invalid_plugin(path, line, value),
+                Ok(value) => value,
+            };
         }
+
+        let vendor: String = captures["vendor"].to_owned();
+        let dep: String = captures["dep"].to_owned();
+
+        Ok(PluginRef::new(vendor, dep, Semver::new(version[0], version[1], version[2])))
     }
 
     pub async fn execute(&self) {
@@ -181,25 +477,25 @@ impl PluginRef {
     }
 }
 
-fn find_pipelines_into(entries: &mut Vec<DirEntry>, path: impl AsRef<Path>) {
-    let dir: ReadDir = match read_dir(path) {
-        Err(error) => panic!("{:?}", error),
+fn find_pipelines_into(entries: &mut Vec<DirEntry>, path: impl AsRef<Path>) -> PipelineResult<()> {
+    let dir: ReadDir = match read_dir(path.as_ref()) {
+        Err(error) => return PipelineError::raise_io_failed(path, error),
         Ok(value) => value,
     };
 
     for entry in dir {
         let entry: DirEntry = match entry {
-            Err(error) => panic!("{:?}", error),
+            Err(error) => return PipelineError::raise_io_failed(path, error),
             Ok(value) => value,
         };
 
         let meta: Metadata = match entry.metadata() {
-            Err(error) => panic!("{:?}", error),
+            Err(error) => return PipelineError::raise_io_failed(entry.path(), error),
             Ok(value) => value,
         };
 
         if meta.is_dir() {
-            find_pipelines_into(entries, entry.path());
+            find_pipelines_into(entries, entry.path())?;
         }
 
         if meta.is_file() {
@@ -210,20 +506,111 @@ fn find_pipelines_into(entries: &mut Vec<DirEntry>, path: impl AsRef<Path>) {
             }
         }
     }
+
+    Ok(())
 }
 
-async fn parse_pipelines_into(pipelines: &mut Vec<Pipeline>, entries: &Vec<DirEntry>) {
+async fn parse_pipelines_into(
+    pipelines: &mut Vec<Pipeline>,
+    root: &Path,
+    entries: &Vec<DirEntry>,
+) -> PipelineResult<()> {
     for entry in entries {
-        pipelines.push(Pipeline::open(entry.path()).await)
+        pipelines.push(Pipeline::open(root, entry.path()).await?)
     }
+
+    Ok(())
 }
 
-pub async fn find_pipelines(path: impl AsRef<Path>) -> Vec<Pipeline> {
+pub async fn find_pipelines(path: impl AsRef<Path>) -> PipelineResult<Vec<Pipeline>> {
     let mut entries: Vec<DirEntry> = Vec::new();
     let mut pipelines: Vec<Pipeline> = Vec::new();
 
-    find_pipelines_into(&mut entries, path);
-    parse_pipelines_into(&mut pipelines, &entries).await;
+    find_pipelines_into(&mut entries, &path)?;
+    parse_pipelines_into(&mut pipelines, path.as_ref(), &entries).await?;
+
+    Ok(pipelines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identify(content: &str) -> PipelineResult<Vec<Task>> {
+        let mut tasks: Vec<Task> = Task::read_all("test.pipeline", content.lines())?;
+        Task::identify_all("test.pipeline", "test.pipeline", &mut tasks)?;
+        Ok(tasks)
+    }
+
+    #[test]
+    fn rejects_oversized_timeout() {
+        let meta: TaskMeta = TaskMeta::parse(0, &["``` etl0/python@3.17.1", "``` timeout: 18446744073709551615h"]);
 
-    pipelines
+        assert_eq!(meta.timeout, None);
+        assert_eq!(meta.warnings, vec!["line 2: invalid timeout '18446744073709551615h'"]);
+    }
+
+    #[test]
+    fn numbers_tasks_sharing_a_plugin() {
+        let content: &str = "``` etl0/python@3.17.1\nprint(1)\n``` etl0/python@3.17.1\nprint(2)";
+        let names: Vec<String> = identify(content).unwrap().into_iter().map(|task| task.name).collect();
+
+        assert_eq!(names, vec!["python", "python-2"]);
+    }
+
+    #[test]
+    fn rejects_generated_name_taken_by_explicit_one() {
+        let content: &str = concat!(
+            "``` etl0/python@3.17.1\n``` name: python-2\nprint(1)\n",
+            "``` etl0/python@3.17.1\nprint(2)\n",
+            "``` etl0/python@3.17.1\nprint(3)",
+        );
+
+        match identify(content) {
+            Err(PipelineError::DuplicateTask(_, name, first, second)) => {
+                assert_eq!((name.as_str(), first, second), ("python-2", 1, 6))
+            }
+            other => panic!("unexpected outcome {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rejects_duplicate_explicit_names() {
+        let content: &str = concat!(
+            "``` etl0/python@3.17.1\n``` name: load\nprint(1)\n",
+            "``` etl0/python@3.17.1\n``` name: load\nprint(2)",
+        );
+
+        assert_eq!(identify(content).unwrap_err().code(), "E106");
+    }
+
+    #[test]
+    fn rejects_invalid_explicit_name() {
+        let content: &str = "``` etl0/python@3.17.1\n``` name: load data\nprint(1)";
+
+        match identify(content) {
+            Err(PipelineError::InvalidTaskName(_, line, name)) => assert_eq!((line, name.as_str()), (1, "load data")),
+            other => panic!("unexpected outcome {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn identifies_tasks_independently_of_root() {
+        let root: PathBuf = std::env::temp_dir().join(format!("etl0-pipelines-{}", std::process::id()));
+        let nested: PathBuf = root.join("nested");
+
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("load.pipeline"), "``` etl0/python@3.17.1\nprint(1)").unwrap();
+
+        let direct: Vec<Pipeline> = find_pipelines(&root).await.unwrap();
+        let dotted: Vec<Pipeline> = find_pipelines(root.join(".")).await.unwrap();
+        std::fs::remove_dir_all(&root).unwrap();
+
+        let first: &Task = direct[0].tasks().next().unwrap();
+        let second: &Task = dotted[0].tasks().next().unwrap();
+
+        assert_ne!(direct[0].path, dotted[0].path);
+        assert_eq!(first.id, Task::identity("nested/load.pipeline", "python"));
+        assert_eq!(first.id, second.id);
+    }
 }
@@ -0,0 +1,3 @@
+pub use crate::docker::{DockerClient, DockerError, DockerResult};
+pub use crate::pipeline::{find_pipelines, Pipeline, PipelineError, PipelineResult, PluginRef, Semver, Task, TaskFile};
+pub use crate::tar::{TarArchive, TarChunk, TarError, TarStream};
@@ -1,27 +1,98 @@
+use std::fs::Metadata;
+use std::os::unix::fs::MetadataExt;
+
 use super::{
     error::{TarError, TarResult},
     stream::TarStream,
 };
 
-pub enum TarEntry {
+pub(crate) enum TarEntry {
     File(String),
+    Data(String, Vec<u8>),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TarOwnership {
+    preserve: bool,
+    uid: u32,
+    gid: u32,
+    uname: Option<String>,
+    gname: Option<String>,
+}
+
+impl TarOwnership {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn preserve(self, value: bool) -> Self {
+        Self {
+            preserve: value,
+            ..self
+        }
+    }
+
+    pub fn uid(self, value: u32) -> Self {
+        Self { uid: value, ..self }
+    }
+
+    pub fn gid(self, value: u32) -> Self {
+        Self { gid: value, ..self }
+    }
+
+    pub fn uname(self, value: &str) -> Self {
+        Self {
+            uname: Some(value.to_owned()),
+            ..self
+        }
+    }
+
+    pub fn gname(self, value: &str) -> Self {
+        Self {
+            gname: Some(value.to_owned()),
+            ..self
+        }
+    }
+
+    pub(crate) fn ids(&self, metadata: Option<&Metadata>) -> (u32, u32) {
+        match metadata {
+            Some(metadata) if self.preserve => (metadata.uid(), metadata.gid()),
+            _ => (self.uid, self.gid),
+        }
+    }
+
+    pub(crate) fn names(&self) -> (Option<&str>, Option<&str>) {
+        (self.uname.as_deref(), self.gname.as_deref())
+    }
 }
 
 pub struct TarArchive {
     entries: Vec<TarEntry>,
+    ownership: TarOwnership,
 }
 
 impl TarArchive {
     pub fn new() -> Self {
-        Self { entries: Vec::new() }
+        Self {
+            entries: Vec::new(),
+            ownership: TarOwnership::default(),
+        }
+    }
+
+    pub fn set_ownership(&mut self, ownership: TarOwnership) {
+        self.ownership = ownership;
     }
 
     pub fn append_file(&mut self, file: String) {
         self.entries.push(TarEntry::File(file));
     }
 
+    pub fn append_data(&mut self, path: String, data: Vec<u8>) {
+        self.entries.push(TarEntry::Data(path, data));
+    }
+
     pub fn into_stream(self, buffer_size: usize) -> TarStream {
-        TarStream::new(self.entries, buffer_size)
+        TarStream::new(self.entries, self.ownership, buffer_size)
     }
 }
 
@@ -32,15 +103,15 @@ pub enum TarChunk {
 }
 
 impl TarChunk {
-    pub fn header(path: String, data: Box<[u8; 512]>) -> Self {
+    pub(crate) fn header(path: String, data: Box<[u8; 512]>) -> Self {
         TarChunk::Header(path, data)
     }
 
-    pub fn padding(index: usize) -> Self {
+    pub(crate) fn padding(index: usize) -> Self {
         TarChunk::Padding(index)
     }
 
-    pub fn data(pages: usize) -> Self {
+    pub(crate) fn data(pages: usize) -> Self {
         TarChunk::Data(vec![0; pages * 512])
     }
 
@@ -52,7 +123,11 @@ impl TarChunk {
         }
     }
 
-    pub fn offset(&mut self, value: usize) -> TarResult<&mut [u8]> {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub(crate) fn offset(&mut self, value: usize) -> TarResult<&mut [u8]> {
         match self {
             TarChunk::Padding(_) => Err(TarError::memory_access(format!(
                 "Padding cannot provide offset, but requested {value}"
@@ -2,17 +2,32 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum TarError {
-    #[error("Cannot process file, because '{0}'")]
-    IOFailed(std::io::Error),
+    #[error("[E051] Cannot process file")]
+    IOFailed(#[source] std::io::Error),
 
-    #[error("Cannot safely access memory, because '{0}'")]
+    #[error("[E052] Cannot safely access memory, because '{0}'")]
     MemoryAccess(String),
+
+    #[error("[E053] Cannot read tar archive, because '{0}'")]
+    InvalidArchive(String),
 }
 
 impl TarError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            TarError::IOFailed(..) => "E051",
+            TarError::MemoryAccess(..) => "E052",
+            TarError::InvalidArchive(..) => "E053",
+        }
+    }
+
     pub fn memory_access(info: impl AsRef<str>) -> TarError {
         TarError::MemoryAccess(info.as_ref().to_owned())
     }
+
+    pub fn invalid_archive(info: impl AsRef<str>) -> TarError {
+        TarError::InvalidArchive(info.as_ref().to_owned())
+    }
 }
 
 pub type TarResult<T> = Result<T, TarError>;
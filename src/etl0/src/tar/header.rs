@@ -2,9 +2,13 @@ use std::fmt::{LowerHex, Octal};
 use std::fs::Metadata;
 use std::os::unix::fs::{MetadataExt, PermissionsExt};
 
-use super::core::TarChunk;
+use super::core::{TarChunk, TarOwnership};
 use super::error::{TarError, TarResult};
 
+// in-memory entries have no file to take the mtime from, a fixed
+// one keeps archives built from the same data byte for byte equal
+const TAR_DATA_MTIME: i64 = 0;
+
 pub struct TarHeader {
     path: String,
     data: Box<[u8; 512]>,
@@ -103,8 +107,8 @@ impl TarHeader {
         Self::write_bytes(header, 0, 99, path.as_bytes())
     }
 
-    fn write_mode(header: &mut [u8; 512], metadata: &Metadata) -> TarResult<()> {
-        Self::write_octal(header, 100, 8, metadata.permissions().mode() & 0o777)
+    fn write_mode(header: &mut [u8; 512], mode: u32) -> TarResult<()> {
+        Self::write_octal(header, 100, 8, mode & 0o777)
     }
 
     fn write_uid(header: &mut [u8; 512], uid: u32) -> TarResult<()> {
@@ -115,12 +119,12 @@ impl TarHeader {
         Self::write_octal(header, 116, 8, gid)
     }
 
-    fn write_size(header: &mut [u8; 512], metadata: &Metadata) -> TarResult<()> {
-        Self::write_octal(header, 124, 12, metadata.size())
+    fn write_size(header: &mut [u8; 512], size: u64) -> TarResult<()> {
+        Self::write_octal(header, 124, 12, size)
     }
 
-    fn write_mtime(header: &mut [u8; 512], metadata: &Metadata) -> TarResult<()> {
-        Self::write_octal(header, 136, 12, metadata.mtime())
+    fn write_mtime(header: &mut [u8; 512], mtime: i64) -> TarResult<()> {
+        Self::write_octal(header, 136, 12, mtime)
     }
 
     fn write_chksum(header: &mut [u8; 512]) -> TarResult<()> {
@@ -132,6 +136,14 @@ impl TarHeader {
         Self::write_bytes(header, 156, 1, b"0")
     }
 
+    fn write_uname(header: &mut [u8; 512], uname: &str) -> TarResult<()> {
+        Self::write_bytes(header, 265, 32, uname.as_bytes())
+    }
+
+    fn write_gname(header: &mut [u8; 512], gname: &str) -> TarResult<()> {
+        Self::write_bytes(header, 297, 32, gname.as_bytes())
+    }
+
     fn write_magic(header: &mut [u8; 512]) -> TarResult<()> {
         Self::write_bytes(header, 257, 8, b"ustar  \0")
     }
@@ -146,17 +158,44 @@ impl TarHeader {
         checksum
     }
 
-    pub fn write(mut self, metadata: &Metadata) -> TarResult<TarChunk> {
+    pub fn write(self, metadata: &Metadata, ownership: &TarOwnership) -> TarResult<TarChunk> {
+        let mode: u32 = metadata.permissions().mode();
+        self.write_values(mode, metadata.size(), metadata.mtime(), ownership, Some(metadata))
+    }
+
+    pub fn write_data(self, length: usize, ownership: &TarOwnership) -> TarResult<TarChunk> {
+        self.write_values(0o644, length as u64, TAR_DATA_MTIME, ownership, None)
+    }
+
+    fn write_values(
+        mut self,
+        mode: u32,
+        size: u64,
+        mtime: i64,
+        ownership: &TarOwnership,
+        metadata: Option<&Metadata>,
+    ) -> TarResult<TarChunk> {
         let data = &mut self.data;
+        let (uid, gid) = ownership.ids(metadata);
+        let (uname, gname) = ownership.names();
 
         Self::write_name(data, &self.path)?;
-        Self::write_mode(data, metadata)?;
-        Self::write_uid(data, 0)?;
-        Self::write_gid(data, 0)?;
-        Self::write_size(data, metadata)?;
-        Self::write_mtime(data, metadata)?;
+        Self::write_mode(data, mode)?;
+        Self::write_uid(data, uid)?;
+        Self::write_gid(data, gid)?;
+        Self::write_size(data, size)?;
+        Self::write_mtime(data, mtime)?;
         Self::write_magic(data)?;
         Self::write_type_flag(data)?;
+
+        if let Some(uname) = uname {
+            Self::write_uname(data, uname)?;
+        }
+
+        if let Some(gname) = gname {
+            Self::write_gname(data, gname)?;
+        }
+
         Self::write_chksum(data)?;
 
         Ok(self.into())
@@ -1,9 +1,11 @@
 mod core;
 mod error;
 mod header;
+mod reader;
 mod state;
 mod stream;
 
-pub use self::core::{TarArchive, TarChunk};
+pub use self::core::{TarArchive, TarChunk, TarOwnership};
 pub use self::error::TarError;
+pub use self::reader::{TarEntryKind, TarExtraction, TarReader};
 pub use self::stream::TarStream;
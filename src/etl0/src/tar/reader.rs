@@ -0,0 +1,379 @@
+use std::fs::Permissions;
+use std::mem;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Component, Path, PathBuf};
+
+use tokio::fs::{create_dir_all, set_permissions, File};
+use tokio::io::AsyncWriteExt;
+
+use super::error::{TarError, TarResult};
+
+// long names and pax records are kept in memory until
+// the entry they describe arrives, so their size is capped
+const TAR_EXTENDED_LIMIT: u64 = 1 << 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TarEntryKind {
+    HardLink,
+    Symlink,
+    CharDevice,
+    BlockDevice,
+    Fifo,
+    Unknown(u8),
+}
+
+impl TarEntryKind {
+    fn from(kind: u8) -> Self {
+        match kind {
+            b'1' => TarEntryKind::HardLink,
+            b'2' => TarEntryKind::Symlink,
+            b'3' => TarEntryKind::CharDevice,
+            b'4' => TarEntryKind::BlockDevice,
+            b'6' => TarEntryKind::Fifo,
+            kind => TarEntryKind::Unknown(kind),
+        }
+    }
+}
+
+// entries lists extracted files and directories, while skipped lists
+// entries which were present in the archive but not written to disk
+#[derive(Debug, Default)]
+pub struct TarExtraction {
+    pub entries: Vec<String>,
+    pub skipped: Vec<(String, TarEntryKind)>,
+}
+
+enum TarReaderState {
+    Header,
+    File(File, u64, u64),
+    Extended(u8, Vec<u8>, u64, u64),
+    Skip(u64),
+}
+
+pub struct TarReader {
+    target: PathBuf,
+    header: Vec<u8>,
+    state: TarReaderState,
+    long_name: Option<String>,
+    extraction: TarExtraction,
+    completed: bool,
+}
+
+impl TarReader {
+    pub fn new(target: impl AsRef<Path>) -> Self {
+        Self {
+            target: target.as_ref().to_path_buf(),
+            header: Vec::with_capacity(512),
+            state: TarReaderState::Header,
+            long_name: None,
+            extraction: TarExtraction::default(),
+            completed: false,
+        }
+    }
+
+    pub async fn write(&mut self, mut data: &[u8]) -> TarResult<()> {
+        while !data.is_empty() && !self.completed {
+            let state: TarReaderState = mem::replace(&mut self.state, TarReaderState::Header);
+
+            self.state = match state {
+                TarReaderState::Header => {
+                    let take: usize = std::cmp::min(512 - self.header.len(), data.len());
+
+                    self.header.extend_from_slice(&data[..take]);
+                    data = &data[take..];
+
+                    match self.header.len() {
+                        512 => self.read_header().await?,
+                        _ => TarReaderState::Header,
+                    }
+                }
+                TarReaderState::File(mut file, left, padding) => {
+                    let take: usize = std::cmp::min(left, data.len() as u64) as usize;
+
+                    if let Err(error) = file.write_all(&data[..take]).await {
+                        return Err(TarError::IOFailed(error));
+                    }
+
+                    data = &data[take..];
+                    Self::next_file(file, left - take as u64, padding).await?
+                }
+                TarReaderState::Extended(kind, mut buffer, left, padding) => {
+                    let take: usize = std::cmp::min(left, data.len() as u64) as usize;
+
+                    buffer.extend_from_slice(&data[..take]);
+                    data = &data[take..];
+
+                    match left - take as u64 {
+                        0 => {
+                            self.long_name = Self::parse_extended(kind, &buffer).or(self.long_name.take());
+                            TarReaderState::Skip(padding)
+                        }
+                        left => TarReaderState::Extended(kind, buffer, left, padding),
+                    }
+                }
+                TarReaderState::Skip(left) => {
+                    let take: usize = std::cmp::min(left, data.len() as u64) as usize;
+
+                    data = &data[take..];
+                    Self::next_skip(left - take as u64)
+                }
+            };
+        }
+
+        Ok(())
+    }
+
+    pub async fn finish(self) -> TarResult<TarExtraction> {
+        match self.state {
+            TarReaderState::Header if self.header.is_empty() => Ok(self.extraction),
+            TarReaderState::Skip(0) => Ok(self.extraction),
+            _ => Err(TarError::invalid_archive("Archive ended in the middle of an entry")),
+        }
+    }
+
+    async fn next_file(mut file: File, left: u64, padding: u64) -> TarResult<TarReaderState> {
+        if left > 0 {
+            return Ok(TarReaderState::File(file, left, padding));
+        }
+
+        match file.flush().await {
+            Ok(()) => Ok(Self::next_skip(padding)),
+            Err(error) => Err(TarError::IOFailed(error)),
+        }
+    }
+
+    fn next_skip(left: u64) -> TarReaderState {
+        match left {
+            0 => TarReaderState::Header,
+            left => TarReaderState::Skip(left),
+        }
+    }
+
+    async fn read_header(&mut self) -> TarResult<TarReaderState> {
+        let header: Vec<u8> = mem::take(&mut self.header);
+
+        // two empty blocks terminate the archive, but
+        // the first one is enough to stop reading
+        if header.iter().all(|value| *value == 0) {
+            self.completed = true;
+            return Ok(TarReaderState::Header);
+        }
+
+        let checksum: u64 = Self::parse_octal(&header[148..156])?;
+        let calculated: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(index, value)| if (148..156).contains(&index) { 32 } else { *value as u64 })
+            .sum();
+
+        if checksum != calculated {
+            return Err(TarError::invalid_archive(format!(
+                "Header checksum mismatch, expected {checksum}, calculated {calculated}"
+            )));
+        }
+
+        let size: u64 = Self::parse_octal(&header[124..136])?;
+        let padding: u64 = (512 - size % 512) % 512;
+
+        let name: String = match self.long_name.take() {
+            Some(name) => name,
+            None => Self::parse_name(&header),
+        };
+
+        match header[156] {
+            b'0' | b'\0' | b'7' => {
+                let path: PathBuf = self.resolve(&name)?;
+                let mode: u32 = Self::parse_octal(&header[100..108])? as u32;
+
+                if let Some(parent) = path.parent() {
+                    if let Err(error) = create_dir_all(parent).await {
+                        return Err(TarError::IOFailed(error));
+                    }
+                }
+
+                let file: File = match File::create(&path).await {
+                    Ok(file) => file,
+                    Err(error) => return Err(TarError::IOFailed(error)),
+                };
+
+                if let Err(error) = set_permissions(&path, Permissions::from_mode(mode & 0o777)).await {
+                    return Err(TarError::IOFailed(error));
+                }
+
+                self.extraction.entries.push(name);
+                Self::next_file(file, size, padding).await
+            }
+            b'5' => {
+                if let Err(error) = create_dir_all(self.resolve(&name)?).await {
+                    return Err(TarError::IOFailed(error));
+                }
+
+                self.extraction.entries.push(name);
+                Ok(Self::next_skip(size + padding))
+            }
+            b'L' | b'x' if size > TAR_EXTENDED_LIMIT => Err(TarError::invalid_archive(format!(
+                "Extended header of {size} bytes exceeds the limit of {TAR_EXTENDED_LIMIT} bytes"
+            ))),
+            kind @ (b'L' | b'x') => Ok(TarReaderState::Extended(kind, Vec::new(), size, padding)),
+            // global headers carry no entry of their own
+            b'g' => Ok(Self::next_skip(size + padding)),
+            // links and devices could point anywhere on the host,
+            // so they are never created, only reported back
+            kind => {
+                self.extraction.skipped.push((name, TarEntryKind::from(kind)));
+                Ok(Self::next_skip(size + padding))
+            }
+        }
+    }
+
+    fn resolve(&self, name: &str) -> TarResult<PathBuf> {
+        let mut path: PathBuf = self.target.clone();
+
+        for component in Path::new(name).components() {
+            match component {
+                Component::Normal(value) => path.push(value),
+                Component::RootDir | Component::CurDir => (),
+                _ => {
+                    return Err(TarError::invalid_archive(format!(
+                        "Entry '{name}' points outside of the target directory"
+                    )))
+                }
+            }
+        }
+
+        Ok(path)
+    }
+
+    fn parse_string(data: &[u8]) -> String {
+        let end: usize = data.iter().position(|value| *value == 0).unwrap_or(data.len());
+        String::from_utf8_lossy(&data[..end]).into_owned()
+    }
+
+    fn parse_name(header: &[u8]) -> String {
+        let name: String = Self::parse_string(&header[0..100]);
+
+        // only posix ustar headers keep the prefix,
+        // gnu headers use this area for other fields
+        match &header[257..263] {
+            b"ustar\0" => match Self::parse_string(&header[345..500]) {
+                prefix if prefix.is_empty() => name,
+                prefix => format!("{prefix}/{name}"),
+            },
+            _ => name,
+        }
+    }
+
+    fn parse_octal(data: &[u8]) -> TarResult<u64> {
+        // gnu base-256 encoding for values not fitting octal
+        if let Some(first) = data.first() {
+            if first & 0x80 != 0 {
+                let value: u64 = data[1..]
+                    .iter()
+                    .fold((*first & 0x7f) as u64, |acc, value| acc << 8 | *value as u64);
+                return Ok(value);
+            }
+        }
+
+        let text: String = Self::parse_string(data);
+        let text: &str = text.trim_matches(' ');
+
+        if text.is_empty() {
+            return Ok(0);
+        }
+
+        match u64::from_str_radix(text, 8) {
+            Ok(value) => Ok(value),
+            Err(_) => Err(TarError::invalid_archive(format!(
+                "Header contains invalid octal '{text}'"
+            ))),
+        }
+    }
+
+    fn parse_extended(kind: u8, data: &[u8]) -> Option<String> {
+        if kind == b'L' {
+            return Some(Self::parse_string(data));
+        }
+
+        // pax records have the form "<length> <key>=<value>\n"
+        let text: String = String::from_utf8_lossy(data).into_owned();
+
+        for record in text.lines() {
+            if let Some((_, record)) = record.split_once(' ') {
+                if let Some(("path", value)) = record.split_once('=') {
+                    return Some(value.to_owned());
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn header(name: &str, kind: u8, size: u64) -> Vec<u8> {
+        let mut data: Vec<u8> = vec![0; 512];
+
+        data[..name.len()].copy_from_slice(name.as_bytes());
+        data[100..107].copy_from_slice(b"0000644");
+        data[124..135].copy_from_slice(format!("{size:011o}").as_bytes());
+        data[156] = kind;
+
+        let checksum: u64 = data
+            .iter()
+            .enumerate()
+            .map(|(index, value)| if (148..156).contains(&index) { 32 } else { *value as u64 })
+            .sum();
+
+        data[148..155].copy_from_slice(format!("{checksum:07o}").as_bytes());
+        data
+    }
+
+    fn target(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("etl0-reader-{}-{name}", std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_extended_header() {
+        let mut reader: TarReader = TarReader::new(target("oversized"));
+        let data: Vec<u8> = header("././@LongLink", b'L', TAR_EXTENDED_LIMIT + 1);
+
+        match reader.write(&data).await {
+            Err(TarError::InvalidArchive(message)) => assert!(message.contains("exceeds the limit")),
+            other => panic!("unexpected outcome {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_skipped_links_and_devices() {
+        let path: PathBuf = target("skipped");
+        let mut reader: TarReader = TarReader::new(&path);
+
+        let data: Vec<u8> = [
+            header("data/", b'5', 0),
+            header("data/link", b'2', 0),
+            header("data/hard", b'1', 0),
+            header("data/null", b'3', 0),
+            vec![0; 1024],
+        ]
+        .concat();
+
+        reader.write(&data).await.unwrap();
+        let extraction: TarExtraction = reader.finish().await.unwrap();
+
+        assert_eq!(extraction.entries, vec![String::from("data/")]);
+        assert_eq!(
+            extraction.skipped,
+            vec![
+                (String::from("data/link"), TarEntryKind::Symlink),
+                (String::from("data/hard"), TarEntryKind::HardLink),
+                (String::from("data/null"), TarEntryKind::CharDevice),
+            ]
+        );
+
+        assert!(!path.join("data/link").exists());
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
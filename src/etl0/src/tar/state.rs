@@ -7,7 +7,7 @@ use tokio::fs::File;
 use tokio::io::AsyncRead;
 use tokio::io::ReadBuf;
 
-use super::core::{TarChunk, TarEntry};
+use super::core::{TarChunk, TarEntry, TarOwnership};
 use super::error::{TarError, TarResult};
 use super::header::TarHeader;
 
@@ -31,22 +31,22 @@ impl TarStateHandler for TarStateInit {
 
 pub struct TarStateOpen {
     buffer_size: usize,
+    ownership: TarOwnership,
     task: Pin<Box<dyn Future<Output = Result<(String, File), std::io::Error>> + Send>>,
 }
 
 impl TarStateOpen {
-    fn new(buffer_size: usize, entry: TarEntry) -> Self {
+    fn new(buffer_size: usize, ownership: TarOwnership, path: String) -> Self {
         let task = async move {
-            match entry {
-                TarEntry::File(path) => match File::open(&path).await {
-                    Ok(file) => Ok((path, file)),
-                    Err(error) => Err(error),
-                },
+            match File::open(&path).await {
+                Ok(file) => Ok((path, file)),
+                Err(error) => Err(error),
             }
         };
 
         Self {
             buffer_size: buffer_size,
+            ownership: ownership,
             task: Box::pin(task),
         }
     }
@@ -60,18 +60,19 @@ impl TarStateHandler for TarStateOpen {
             Poll::Ready(Ok((path, file))) => (path, file),
         };
 
-        TarStateHeader::new(self.buffer_size, path, file).poll(cx)
+        TarStateHeader::new(self.buffer_size, self.ownership, path, file).poll(cx)
     }
 }
 
 pub struct TarStateHeader {
     buffer_size: usize,
+    ownership: TarOwnership,
     path: String,
     task: Pin<Box<dyn Future<Output = Result<(File, Metadata), std::io::Error>> + Send>>,
 }
 
 impl TarStateHeader {
-    fn new<'a>(buffer_size: usize, path: String, file: File) -> TarStateHeader {
+    fn new<'a>(buffer_size: usize, ownership: TarOwnership, path: String, file: File) -> TarStateHeader {
         let task = async move {
             match file.metadata().await {
                 Ok(metadata) => Ok((file, metadata)),
@@ -83,6 +84,7 @@ impl TarStateHeader {
             path: path,
             task: Box::pin(task),
             buffer_size: buffer_size,
+            ownership: ownership,
         }
     }
 }
@@ -98,7 +100,7 @@ impl TarStateHandler for TarStateHeader {
         let length: u64 = metadata.len();
         let header: TarHeader = TarHeader::empty(self.path);
 
-        match header.write(&metadata) {
+        match header.write(&metadata, &self.ownership) {
             Ok(chunk) => TarState::read(self.buffer_size, file, length).ready(chunk),
             Err(error) => TarState::failed(error),
         }
@@ -195,6 +197,50 @@ impl TarStateHandler for TarStateRead {
     }
 }
 
+pub struct TarStateMemory {
+    path: String,
+    data: Vec<u8>,
+    ownership: TarOwnership,
+    written: bool,
+}
+
+impl TarStateMemory {
+    fn new(ownership: TarOwnership, path: String, data: Vec<u8>) -> Self {
+        Self {
+            path: path,
+            data: data,
+            ownership: ownership,
+            written: false,
+        }
+    }
+
+    fn next(self) -> Self {
+        Self { written: true, ..self }
+    }
+}
+
+impl TarStateHandler for TarStateMemory {
+    fn poll(self, _cx: &mut Context<'_>) -> TarPollResult {
+        if !self.written {
+            let header: TarHeader = TarHeader::empty(self.path.clone());
+            let length: usize = self.data.len();
+
+            return match header.write_data(length, &self.ownership) {
+                Ok(chunk) if length == 0 => TarState::init().ready(chunk),
+                Ok(chunk) => TarState::Memory(self.next()).ready(chunk),
+                Err(error) => TarState::failed(error),
+            };
+        }
+
+        let mut data: Vec<u8> = self.data;
+        let length: usize = data.len();
+
+        // file content is always aligned to the full page
+        data.resize(length.div_ceil(512) * 512, 0);
+        TarState::init().ready(TarChunk::Data(data))
+    }
+}
+
 pub struct TarStatePadding {
     index: usize,
 }
@@ -237,6 +283,7 @@ pub enum TarState {
     Open(TarStateOpen),
     Header(TarStateHeader),
     Read(TarStateRead),
+    Memory(TarStateMemory),
     Padding(TarStatePadding),
     Completed(TarStateCompleted),
 }
@@ -254,8 +301,11 @@ impl TarState {
         TarState::Padding(TarStatePadding::new())
     }
 
-    pub fn open(buffer_size: usize, entry: TarEntry) -> Self {
-        TarState::Open(TarStateOpen::new(buffer_size, entry))
+    pub fn open(buffer_size: usize, ownership: &TarOwnership, entry: TarEntry) -> Self {
+        match entry {
+            TarEntry::File(path) => TarState::Open(TarStateOpen::new(buffer_size, ownership.clone(), path)),
+            TarEntry::Data(path, data) => TarState::Memory(TarStateMemory::new(ownership.clone(), path, data)),
+        }
     }
 
     pub fn read(buffer_size: usize, file: File, length: u64) -> Self {
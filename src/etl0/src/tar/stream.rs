@@ -5,21 +5,23 @@ use std::task::{Context, Poll};
 
 use futures::Stream;
 
-use super::core::{TarChunk, TarEntry};
+use super::core::{TarChunk, TarEntry, TarOwnership};
 use super::state::{TarPollResult, TarStateHandler};
 use super::{error::TarResult, state::TarState};
 
 pub struct TarStream {
     state: TarState,
     buffer_size: usize,
+    ownership: TarOwnership,
     entries: VecDeque<TarEntry>,
 }
 
 impl TarStream {
-    pub fn new(entries: Vec<TarEntry>, buffer_size: usize) -> Self {
+    pub(crate) fn new(entries: Vec<TarEntry>, ownership: TarOwnership, buffer_size: usize) -> Self {
         Self {
             state: TarState::init(),
             buffer_size: buffer_size / 512 * 512,
+            ownership,
             entries: entries.into(),
         }
     }
@@ -40,6 +42,7 @@ impl Stream for TarStream {
                 TarState::Open(state) => state.poll(cx),
                 TarState::Header(state) => state.poll(cx),
                 TarState::Read(state) => state.poll(cx),
+                TarState::Memory(state) => state.poll(cx),
                 TarState::Padding(state) => state.poll(cx),
                 TarState::Completed(state) => state.poll(cx),
             };
@@ -49,7 +52,7 @@ impl Stream for TarStream {
                 TarPollResult::ReturnPolling(state, poll) => (state, Some(poll)),
                 TarPollResult::NextEntry() => match self_mut.entries.pop_front() {
                     None => (TarState::padding(), None),
-                    Some(entry) => (TarState::open(self_mut.buffer_size, entry), None),
+                    Some(entry) => (TarState::open(self_mut.buffer_size, &self_mut.ownership, entry), None),
                 },
             };
 
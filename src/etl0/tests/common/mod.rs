@@ -0,0 +1,121 @@
+// a fake daemon listening on a unix socket, it answers every request
+// from a table of canned responses matched by the end of the path
+#![allow(dead_code)]
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{UnixListener, UnixStream};
+
+use etl0::docker::DockerClient;
+
+pub struct FakeResponse {
+    pub path: &'static str,
+    pub status: u16,
+    pub body: Vec<u8>,
+}
+
+fn socket() -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let index: usize = COUNTER.fetch_add(1, Ordering::SeqCst);
+
+    std::env::temp_dir().join(format!("etl0-fake-{}-{index}.sock", std::process::id()))
+}
+
+pub fn frame(kind: u8, payload: &[u8]) -> Vec<u8> {
+    let mut data: Vec<u8> = vec![kind, 0, 0, 0];
+    data.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    data.extend_from_slice(payload);
+    data
+}
+
+pub fn exec_inspect(code: i64) -> Vec<u8> {
+    let process: &str = r#"{"entrypoint": "cat", "arguments": [], "tty": false}"#;
+    let body: String = format!(
+        r#"{{"ID": "e1", "ContainerID": "c1", "Running": false, "ExitCode": {code}, "Pid": 0,
+            "OpenStdin": false, "OpenStdout": true, "OpenStderr": true, "ProcessConfig": {process}}}"#
+    );
+
+    body.into_bytes()
+}
+
+async fn serve(listener: UnixListener, responses: Vec<FakeResponse>, requests: &'static AtomicUsize) {
+    let responses: &'static [FakeResponse] = Vec::leak(responses);
+
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(answer(stream, responses, requests));
+    }
+}
+
+async fn answer(mut stream: UnixStream, responses: &'static [FakeResponse], requests: &'static AtomicUsize) {
+    let mut data: Vec<u8> = Vec::new();
+    let mut chunk: [u8; 4096] = [0; 4096];
+
+    loop {
+        let head: usize = match data.windows(4).position(|window| window == b"\r\n\r\n") {
+            Some(index) => index + 4,
+            None => match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(count) => {
+                    data.extend_from_slice(&chunk[..count]);
+                    continue;
+                }
+            },
+        };
+
+        let text: String = String::from_utf8_lossy(&data[..head]).to_string();
+        let target: &str = text.split(' ').nth(1).unwrap_or_default();
+        let path: &str = target.split('?').next().unwrap_or_default();
+        let length: usize = text
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(str::to_owned)
+            })
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0);
+
+        while data.len() < head + length {
+            match stream.read(&mut chunk).await {
+                Ok(0) | Err(_) => return,
+                Ok(count) => data.extend_from_slice(&chunk[..count]),
+            }
+        }
+
+        let response: &FakeResponse = match responses.iter().find(|response| path.ends_with(response.path)) {
+            Some(response) => response,
+            None => panic!("unexpected request to '{path}'"),
+        };
+
+        let reply: String = format!(
+            "HTTP/1.1 {} Fake\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n",
+            response.status,
+            response.body.len()
+        );
+
+        data.drain(..head + length);
+        requests.fetch_add(1, Ordering::SeqCst);
+
+        if stream.write_all(reply.as_bytes()).await.is_err() || stream.write_all(&response.body).await.is_err() {
+            return;
+        }
+    }
+}
+
+pub async fn client(responses: Vec<FakeResponse>) -> DockerClient {
+    counted(responses).await.0
+}
+
+// the counter tells how many requests the daemon has answered
+pub async fn counted(responses: Vec<FakeResponse>) -> (DockerClient, &'static AtomicUsize) {
+    let path: PathBuf = socket();
+    let _ = std::fs::remove_file(&path);
+
+    let requests: &'static AtomicUsize = Box::leak(Box::new(AtomicUsize::new(0)));
+    let listener: UnixListener = UnixListener::bind(&path).unwrap();
+    tokio::spawn(serve(listener, responses, requests));
+
+    (DockerClient::open(path.to_str().unwrap()), requests)
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use tokio_stream::StreamExt;
+
+use self::common::{client, exec_inspect, frame, FakeResponse};
+use etl0::docker::*;
+
+async fn collect<S: StreamExt<Item = DockerResult<hyper::body::Bytes>> + Unpin>(
+    mut stream: S,
+) -> DockerResult<Vec<u8>> {
+    let mut data: Vec<u8> = Vec::new();
+
+    while let Some(item) = stream.next().await {
+        data.extend_from_slice(&item?);
+    }
+
+    Ok(data)
+}
+
+#[tokio::test]
+async fn archive_fails_when_tar_exits_non_zero() {
+    let output: Vec<u8> = [
+        frame(1, b"partial"),
+        frame(2, b"tar: /missing: Cannot stat: No such file or directory\n"),
+    ]
+    .concat();
+
+    let client: DockerClient = client(vec![
+        FakeResponse {
+            path: "/containers/c1/exec",
+            status: 201,
+            body: br#"{"Id": "e1"}"#.to_vec(),
+        },
+        FakeResponse {
+            path: "/exec/e1/start",
+            status: 200,
+            body: output,
+        },
+        FakeResponse {
+            path: "/exec/e1/json",
+            status: 200,
+            body: exec_inspect(2),
+        },
+    ])
+    .await;
+
+    let stream: ContainerArchiveStream = match client.container_archive("c1", &["/missing"]).await.unwrap() {
+        ContainerArchive::Succeeded(stream) => stream,
+        other => panic!("unexpected outcome {other:?}"),
+    };
+
+    match collect(stream).await {
+        Err(DockerError::ExecFailed(id, code, stderr)) => {
+            assert_eq!((id.as_str(), code), ("e1", 2));
+            assert_eq!(stderr, "tar: /missing: Cannot stat: No such file or directory");
+        }
+        other => panic!("unexpected outcome {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn archive_succeeds_when_tar_exits_zero() {
+    let client: DockerClient = client(vec![
+        FakeResponse {
+            path: "/containers/c1/exec",
+            status: 201,
+            body: br#"{"Id": "e1"}"#.to_vec(),
+        },
+        FakeResponse {
+            path: "/exec/e1/start",
+            status: 200,
+            body: [frame(1, b"archive"), frame(1, b" bytes")].concat(),
+        },
+        FakeResponse {
+            path: "/exec/e1/json",
+            status: 200,
+            body: exec_inspect(0),
+        },
+    ])
+    .await;
+
+    let stream: ContainerArchiveStream = match client.container_archive("c1", &["/data"]).await.unwrap() {
+        ContainerArchive::Succeeded(stream) => stream,
+        other => panic!("unexpected outcome {other:?}"),
+    };
+
+    assert_eq!(collect(stream).await.unwrap(), b"archive bytes");
+}
+
+#[tokio::test]
+async fn read_file_fails_when_file_is_missing() {
+    let client: DockerClient = client(vec![
+        FakeResponse {
+            path: "/containers/c1/exec",
+            status: 201,
+            body: br#"{"Id": "e1"}"#.to_vec(),
+        },
+        FakeResponse {
+            path: "/exec/e1/start",
+            status: 200,
+            body: frame(2, b"cat: /missing.txt: No such file or directory\n"),
+        },
+        FakeResponse {
+            path: "/exec/e1/json",
+            status: 200,
+            body: exec_inspect(1),
+        },
+    ])
+    .await;
+
+    let stream: ContainerArchiveStream = match client.container_read_file("c1", "/missing.txt").await.unwrap() {
+        ContainerReadFile::Succeeded(stream) => stream,
+        other => panic!("unexpected outcome {other:?}"),
+    };
+
+    match collect(stream).await {
+        Err(error) => {
+            assert_eq!(error.code(), "E033");
+            assert!(error
+                .to_string()
+                .contains("cat: /missing.txt: No such file or directory"));
+        }
+        Ok(data) => panic!("unexpected success with {} bytes", data.len()),
+    }
+}
@@ -0,0 +1,304 @@
+// checks serde models against definitions of the Docker Engine API
+// v1.42 swagger.yaml, the version the client pins, converted to json;
+// response schemas the spec declares inline are stored under their
+// title, every model is fed a document carrying all properties of its
+// schema and another one carrying only the required ones, nested
+// objects included, so a type mismatch, a renamed field or a field
+// assumed to be always present fails here instead of at runtime
+
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+
+use etl0::docker::*;
+
+const SPEC: &str = include_str!("openapi/engine-v1.42.json");
+
+// the spec leaves these optional, but every daemon fills them in
+// and the models rely on it
+const ALWAYS_SENT: &[(&str, &[&str])] = &[
+    (
+        "ContainerSummary",
+        &["Id", "Image", "ImageID", "Command", "Created", "Status"],
+    ),
+    (
+        "ContainerInspectResponse",
+        &[
+            "Id",
+            "Created",
+            "Path",
+            "Args",
+            "Image",
+            "Name",
+            "RestartCount",
+            "Mounts",
+        ],
+    ),
+    (
+        "ContainerState",
+        &[
+            "Status",
+            "Running",
+            "Paused",
+            "Restarting",
+            "OOMKilled",
+            "Dead",
+            "Pid",
+            "ExitCode",
+            "Error",
+            "StartedAt",
+            "FinishedAt",
+        ],
+    ),
+    ("Health", &["Status", "FailingStreak"]),
+    ("MountPoint", &["Type", "Source", "Destination", "Mode", "RW"]),
+    ("ContainerConfig", &["Hostname", "User", "Image", "WorkingDir"]),
+    ("NetworkSettings", &["IPAddress", "Gateway"]),
+    ("EndpointSettings", &["NetworkID", "IPAddress", "Gateway", "MacAddress"]),
+    ("ContainerTopResponse", &["Titles", "Processes"]),
+    ("ContainerPruneResponse", &["SpaceReclaimed"]),
+    (
+        "ExecInspectResponse",
+        &[
+            "ID",
+            "ContainerID",
+            "Running",
+            "Pid",
+            "OpenStdin",
+            "OpenStdout",
+            "OpenStderr",
+        ],
+    ),
+    ("ProcessConfig", &["entrypoint", "arguments", "tty"]),
+    ("ImagePruneResponse", &["SpaceReclaimed"]),
+    ("VolumePruneResponse", &["SpaceReclaimed"]),
+    ("NetworkCreateResponse", &["Id"]),
+    ("BuildCache", &["ID", "Type", "Size", "InUse", "Shared"]),
+    (
+        "SystemInfo",
+        &[
+            "ID",
+            "Name",
+            "ServerVersion",
+            "Containers",
+            "ContainersRunning",
+            "Images",
+            "Driver",
+            "DockerRootDir",
+            "NCPU",
+            "MemTotal",
+            "OperatingSystem",
+            "OSType",
+            "Architecture",
+            "KernelVersion",
+        ],
+    ),
+    (
+        "SystemVersion",
+        &["Version", "ApiVersion", "GitCommit", "GoVersion", "Os", "Arch"],
+    ),
+    ("ErrorDetail", &["message"]),
+];
+
+fn always_sent(name: &str, key: &str) -> bool {
+    ALWAYS_SENT
+        .iter()
+        .any(|(definition, keys)| *definition == name && keys.contains(&key))
+}
+
+struct Spec {
+    definitions: Map<String, Value>,
+}
+
+impl Spec {
+    fn load() -> Self {
+        let mut spec: Value = serde_json::from_str(SPEC).expect("vendored spec is not valid json");
+
+        match spec["definitions"].take() {
+            Value::Object(definitions) => Self { definitions },
+            _ => panic!("vendored spec has no definitions"),
+        }
+    }
+
+    fn document(&self, name: &str, minimal: bool) -> Value {
+        match self.definitions.get(name) {
+            Some(schema) => self.sample(name, schema, minimal),
+            None => panic!("definition '{name}' is not vendored"),
+        }
+    }
+
+    // definitions outside the vendored subset are not read by
+    // any model, so they are represented by an empty object
+    fn sample(&self, name: &str, schema: &Value, minimal: bool) -> Value {
+        if let Some(reference) = schema["$ref"].as_str() {
+            let name: &str = reference.trim_start_matches("#/definitions/");
+
+            return match self.definitions.get(name) {
+                Some(schema) => self.sample(name, schema, minimal),
+                None => json!({}),
+            };
+        }
+
+        if let Some(values) = schema["enum"].as_array() {
+            return values[0].clone();
+        }
+
+        match schema["type"].as_str() {
+            Some("string") => json!("value"),
+            Some("integer") => json!(1),
+            Some("number") => json!(1.5),
+            Some("boolean") => json!(true),
+            Some("array") => json!([self.sample(name, &schema["items"], minimal)]),
+            Some("object") | None => self.sample_object(name, schema, minimal),
+            Some(other) => panic!("unsupported type '{other}' in '{name}'"),
+        }
+    }
+
+    fn sample_object(&self, name: &str, schema: &Value, minimal: bool) -> Value {
+        let mut document: Map<String, Value> = Map::new();
+        let required: Vec<&str> = match schema["required"].as_array() {
+            Some(values) => values.iter().filter_map(Value::as_str).collect(),
+            None => Vec::new(),
+        };
+
+        if let Some(properties) = schema["properties"].as_object() {
+            for (key, property) in properties {
+                let needed: bool = required.contains(&key.as_str()) || always_sent(name, key);
+
+                // optional objects still come with their own required fields
+                if !minimal || needed || self.is_object(property) {
+                    document.insert(key.clone(), self.sample(name, property, minimal));
+                }
+            }
+        }
+
+        if !minimal && schema["additionalProperties"].is_object() {
+            document.insert(
+                String::from("key"),
+                self.sample(name, &schema["additionalProperties"], minimal),
+            );
+        }
+
+        Value::Object(document)
+    }
+
+    fn is_object(&self, schema: &Value) -> bool {
+        if let Some(reference) = schema["$ref"].as_str() {
+            return match self.definitions.get(reference.trim_start_matches("#/definitions/")) {
+                Some(schema) => self.is_object(schema),
+                None => false,
+            };
+        }
+
+        match schema["type"].as_str() {
+            Some("object") => schema["properties"].is_object(),
+            Some(_) => false,
+            None => schema["properties"].is_object(),
+        }
+    }
+}
+
+fn check<T: DeserializeOwned>(name: &str) {
+    let spec: Spec = Spec::load();
+
+    for minimal in [false, true] {
+        let document: Value = spec.document(name, minimal);
+
+        if let Err(error) = serde_json::from_value::<T>(document.clone()) {
+            panic!("model of '{name}' drifted from the spec: {error}\n{document:#}");
+        }
+    }
+}
+
+#[test]
+fn error_response_matches_spec() {
+    check::<ErrorResponse>("ErrorResponse");
+}
+
+#[test]
+fn id_responses_match_spec() {
+    check::<ExecCreateResponse>("IdResponse");
+    check::<ContainerCommitResponse>("IdResponse");
+}
+
+#[test]
+fn container_create_response_matches_spec() {
+    check::<ContainerCreateResponse>("ContainerCreateResponse");
+}
+
+#[test]
+fn container_wait_response_matches_spec() {
+    check::<ContainerWaitResponse>("ContainerWaitResponse");
+}
+
+#[test]
+fn container_summary_matches_spec() {
+    check::<ContainerInfo>("ContainerSummary");
+}
+
+#[test]
+fn volume_matches_spec() {
+    check::<VolumeInfo>("Volume");
+    check::<VolumeListResponse>("VolumeListResponse");
+}
+
+#[test]
+fn image_delete_item_matches_spec() {
+    check::<ImageDeleteItem>("ImageDeleteResponseItem");
+}
+
+#[test]
+fn system_version_matches_spec() {
+    check::<SystemVersionResponse>("SystemVersion");
+}
+
+#[test]
+fn container_inspect_response_matches_spec() {
+    check::<ContainerInspectResponse>("ContainerInspectResponse");
+}
+
+#[test]
+fn container_top_and_update_responses_match_spec() {
+    check::<ContainerTopResponse>("ContainerTopResponse");
+    check::<ContainerUpdateResponse>("ContainerUpdateResponse");
+}
+
+#[test]
+fn prune_responses_match_spec() {
+    check::<ContainerPruneResponse>("ContainerPruneResponse");
+    check::<ImagePruneResponse>("ImagePruneResponse");
+    check::<VolumePruneResponse>("VolumePruneResponse");
+    check::<NetworkPruneResponse>("NetworkPruneResponse");
+}
+
+#[test]
+fn exec_inspect_response_matches_spec() {
+    check::<ExecInspectResponse>("ExecInspectResponse");
+}
+
+#[test]
+fn image_summary_matches_spec() {
+    check::<ImageInfo>("ImageSummary");
+}
+
+#[test]
+fn image_stream_items_match_spec() {
+    check::<ImageCreateStreamItem>("CreateImageInfo");
+    check::<ImageCreateStreamItem>("PushImageInfo");
+    check::<ImageBuildStreamItem>("BuildInfo");
+}
+
+#[test]
+fn network_create_response_matches_spec() {
+    check::<NetworkCreateResponse>("NetworkCreateResponse");
+}
+
+#[test]
+fn system_info_matches_spec() {
+    check::<SystemInfoResponse>("SystemInfo");
+}
+
+#[test]
+fn system_df_response_matches_spec() {
+    check::<SystemBuildCacheInfo>("BuildCache");
+    check::<SystemDfResponse>("SystemDataUsageResponse");
+}
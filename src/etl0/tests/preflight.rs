@@ -0,0 +1,64 @@
+mod common;
+
+use self::common::{client, FakeResponse};
+use etl0::docker::*;
+
+fn daemon() -> Vec<FakeResponse> {
+    let info: &str = r#"{"ID": "d1", "Name": "fake", "ServerVersion": "24.0.0", "Containers": 0,
+        "ContainersRunning": 0, "Images": 0, "Driver": "overlay2", "DockerRootDir": "/var/lib/docker",
+        "NCPU": 1, "MemTotal": 1, "OperatingSystem": "linux", "OSType": "linux", "Architecture": "x86_64",
+        "KernelVersion": "6.0", "SecurityOptions": []}"#;
+
+    vec![
+        FakeResponse {
+            path: "/_ping",
+            status: 200,
+            body: b"OK".to_vec(),
+        },
+        FakeResponse {
+            path: "/containers/json",
+            status: 200,
+            body: b"[]".to_vec(),
+        },
+        FakeResponse {
+            path: "/info",
+            status: 200,
+            body: info.as_bytes().to_vec(),
+        },
+    ]
+}
+
+#[tokio::test]
+async fn preflight_checks_only_absolute_bind_sources() {
+    let client: DockerClient = client(daemon()).await;
+    let host: ContainerHostConfig = ContainerHostConfig::default()
+        .bind("data:/var/lib/data")
+        .bind("/etl0-missing-source:/input:ro");
+
+    let spec: ContainerCreateSpec = ContainerCreateSpec {
+        host_config: host,
+        ..ContainerCreateSpec::new("alpine")
+    };
+
+    match client.preflight(&spec).await.unwrap() {
+        SystemPreflight::Failed(problems) => {
+            assert_eq!(problems.len(), 1);
+            assert!(problems[0].starts_with("Cannot bind '/etl0-missing-source'"));
+        }
+        other => panic!("unexpected outcome {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn preflight_accepts_named_volume_binds() {
+    let client: DockerClient = client(daemon()).await;
+    let spec: ContainerCreateSpec = ContainerCreateSpec {
+        host_config: ContainerHostConfig::default().bind("data:/var/lib/data"),
+        ..ContainerCreateSpec::new("alpine")
+    };
+
+    assert!(matches!(
+        client.preflight(&spec).await.unwrap(),
+        SystemPreflight::Succeeded
+    ));
+}
@@ -0,0 +1,44 @@
+mod common;
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use self::common::{counted, FakeResponse};
+use etl0::docker::*;
+
+#[tokio::test]
+async fn pull_ends_on_error_line_without_retry() {
+    let body: &[u8] = concat!(
+        r#"{"status": "Pulling from library/missing", "id": "latest"}"#,
+        "\r\n",
+        r#"{"error": "manifest unknown", "errorDetail": {"message": "manifest unknown"}}"#,
+        "\r\n",
+        r#"{"status": "Digest: sha256:0000"}"#,
+        "\r\n",
+    )
+    .as_bytes();
+
+    let (client, requests): (DockerClient, &AtomicUsize) = counted(vec![FakeResponse {
+        path: "/images/create",
+        status: 200,
+        body: body.to_vec(),
+    }])
+    .await;
+
+    let retry: ImagePullRetry = ImagePullRetry {
+        attempts: 3,
+        backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(1),
+    };
+
+    let mut lines: usize = 0;
+    let image: ImageReference = ImageReference::parse("missing:latest");
+
+    match client.images_pull(&image, None, &retry, |_| lines += 1).await {
+        Ok(ImagePull::Failed(error)) => assert_eq!(error.message, "manifest unknown"),
+        other => panic!("unexpected outcome {other:?}"),
+    }
+
+    assert_eq!(lines, 1);
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+}